@@ -0,0 +1,327 @@
+#!/usr/bin/env python3
+# Small library to find a common prefix among strings.
+# Copyright (C) 2024  Sohum Mendon
+# SPDX-License-Identifier: MIT
+
+"""Generate the Unicode tables in `src/`.
+
+    scripts/gen_tables.py [UCD_DIR]
+
+This reads the Unicode Character Database for `UNICODE_VERSION` from
+`UCD_DIR` (laid out like `https://www.unicode.org/Public/16.0.0/ucd/`),
+or downloads it from unicode.org, and rewrites:
+
+- `src/grapheme/tables.rs`
+- `src/casefold/tables.rs`
+- `src/canonical/tables.rs`
+
+Files whose header names another version are rejected. Run `cargo fmt`
+afterwards.
+"""
+
+import sys
+import urllib.request
+from pathlib import Path
+
+UNICODE_VERSION = "16.0.0"
+BASE_URL = f"https://www.unicode.org/Public/{UNICODE_VERSION}/ucd/"
+
+# Every input, relative to the root of the UCD.
+CASE_FOLDING = "CaseFolding.txt"
+UNICODE_DATA = "UnicodeData.txt"
+GRAPHEME_BREAK = "auxiliary/GraphemeBreakProperty.txt"
+EMOJI_DATA = "emoji/emoji-data.txt"
+DERIVED_CORE = "DerivedCoreProperties.txt"
+
+ROOT = Path(__file__).resolve().parent.parent
+
+HEADER = """\
+// Small library to find a common prefix among strings.
+// Copyright (C) 2024  Sohum Mendon
+// SPDX-License-Identifier: MIT
+"""
+
+SURROGATES = range(0xD800, 0xE000)
+
+# `Grapheme_Cluster_Break` values, and their `Category` in `src/grapheme.rs`.
+GRAPHEME_CATEGORIES = {
+    "CR": "CR",
+    "LF": "LF",
+    "Control": "Control",
+    "Extend": "Extend",
+    "ZWJ": "ZWJ",
+    "Regional_Indicator": "RegionalIndicator",
+    "Prepend": "Prepend",
+    "SpacingMark": "SpacingMark",
+    "L": "L",
+    "V": "V",
+    "T": "T",
+    "LV": "LV",
+    "LVT": "LVT",
+}
+
+
+def read(ucd_dir, name):
+    """Read a UCD file, and check that it is for `UNICODE_VERSION`."""
+    if ucd_dir is None:
+        with urllib.request.urlopen(BASE_URL + name) as response:
+            text = response.read().decode("utf-8")
+    else:
+        text = (Path(ucd_dir) / name).read_text(encoding="utf-8")
+
+    # `UnicodeData.txt` has no header. The others start with their name and
+    # version, except `emoji-data.txt`, which names the emoji version.
+    stem = Path(name).stem
+    if name == EMOJI_DATA:
+        major_minor = UNICODE_VERSION.rsplit(".", 1)[0]
+        expected = f"Emoji Version {major_minor}"
+        header = "".join(text.splitlines(keepends=True)[:10])
+    elif name != UNICODE_DATA:
+        expected = f"{stem}-{UNICODE_VERSION}.txt"
+        header = text.split("\n", 1)[0]
+    else:
+        expected = header = ""
+
+    if expected not in header:
+        sys.exit(f"{name} is not for Unicode {UNICODE_VERSION}")
+
+    return text
+
+
+def records(text):
+    """The fields of every line of a UCD file, without comments."""
+    for line in text.splitlines():
+        line = line.split("#", 1)[0].strip()
+        if line:
+            yield [field.strip() for field in line.split(";")]
+
+
+def code_points(field):
+    """The code points of `XXXX` or `XXXX..YYYY`, without surrogates."""
+    first, _, last = field.partition("..")
+    lo, hi = int(first, 16), int(last or first, 16)
+
+    return [cp for cp in range(lo, hi + 1) if cp not in SURROGATES]
+
+
+def properties(text, prop=None):
+    """Map every listed code point to its value. With `prop`, only the
+    lines of that property are read, from files with a third field."""
+    values = {}
+
+    for fields in records(text):
+        if prop is None:
+            value = fields[1]
+        elif fields[1] == prop:
+            value = fields[2] if len(fields) > 2 else True
+        else:
+            continue
+
+        for cp in code_points(fields[0]):
+            values[cp] = value
+
+    return values
+
+
+def ranges(values):
+    """Merge code points with the same value into `(lo, hi, value)`."""
+    merged = []
+
+    for cp in sorted(values):
+        value = values[cp]
+        if merged and merged[-1][1] + 1 == cp and merged[-1][2] == value:
+            merged[-1][1] = cp
+        else:
+            merged.append([cp, cp, value])
+
+    return merged
+
+
+def char(cp):
+    return "'\\u{%X}'" % cp
+
+
+def string(cps):
+    return '"' + "".join("\\u{%X}" % cp for cp in cps) + '"'
+
+
+def table(doc, name, ty, rows, skip_fmt=True):
+    lines = [f"/// {line}".rstrip() for line in doc.splitlines()]
+    if skip_fmt:
+        lines.append("#[rustfmt::skip]")
+    lines.append(f"pub(super) const {name}: {ty} = &[")
+    lines.extend(f"    {row}," for row in rows)
+    lines.append("];")
+
+    return "\n".join(lines) + "\n"
+
+
+def write(path, doc, *tables):
+    doc = "\n".join(f"//! {line}".rstrip() for line in doc.splitlines())
+    text = HEADER + "\n" + doc + "\n\n" + "\n".join(tables)
+
+    (ROOT / path).write_text(text, encoding="utf-8")
+
+
+def grapheme(ucd_dir):
+    categories = {
+        cp: GRAPHEME_CATEGORIES[value]
+        for cp, value in properties(read(ucd_dir, GRAPHEME_BREAK)).items()
+    }
+    pictographic = properties(read(ucd_dir, EMOJI_DATA), "Extended_Pictographic")
+    incb = properties(read(ucd_dir, DERIVED_CORE), "InCB")
+
+    # `Category` has room for one value per code point, which works because
+    # these properties don't overlap.
+    extra = [(cp, "ExtendedPictographic") for cp in pictographic]
+    extra += [(cp, "InCbConsonant") for cp, v in incb.items() if v == "Consonant"]
+    for cp, category in extra:
+        if cp in categories:
+            sys.exit(f"U+{cp:04X} is both {categories[cp]} and {category}")
+        categories[cp] = category
+
+    extend = {cp: True for cp, v in incb.items() if v == "Extend"}
+    linker = sorted(cp for cp, v in incb.items() if v == "Linker")
+
+    write(
+        "src/grapheme/tables.rs",
+        f"""\
+Grapheme break property tables for Unicode {UNICODE_VERSION}.
+
+Derived from `GraphemeBreakProperty.txt`, `emoji-data.txt` and
+`DerivedCoreProperties.txt` (`Indic_Conjunct_Break`). Code points not
+listed have the `Other` category.
+
+Generated by `scripts/gen_tables.py`.""",
+        """\
+use super::Category::{
+    self, Control, Extend, ExtendedPictographic, InCbConsonant, Prepend, RegionalIndicator,
+    SpacingMark, CR, L, LF, LV, LVT, T, V, ZWJ,
+};
+""",
+        table(
+            "Sorted, non-overlapping ranges of code points and their category.",
+            "CATEGORIES",
+            "&[(char, char, Category)]",
+            [f"({char(lo)}, {char(hi)}, {v})" for lo, hi, v in ranges(categories)],
+        ),
+        table(
+            "Sorted, non-overlapping ranges of code points with\n"
+            "`Indic_Conjunct_Break=Extend`.",
+            "INCB_EXTEND",
+            "&[(char, char)]",
+            [f"({char(lo)}, {char(hi)})" for lo, hi, _ in ranges(extend)],
+        ),
+        table(
+            "Code points with `Indic_Conjunct_Break=Linker`.",
+            "INCB_LINKER",
+            "&[char]",
+            [", ".join(char(cp) for cp in linker)],
+            skip_fmt=False,
+        ),
+    )
+
+
+def casefold(ucd_dir):
+    mappings = {"C": {}, "F": {}, "S": {}}
+
+    for code, status, mapping, *_ in records(read(ucd_dir, CASE_FOLDING)):
+        if status in mappings:
+            mappings[status][int(code, 16)] = [int(cp, 16) for cp in mapping.split()]
+
+    write(
+        "src/casefold/tables.rs",
+        f"""\
+Case folding tables for Unicode {UNICODE_VERSION}.
+
+Derived from the `C`, `F` and `S` mappings in `CaseFolding.txt`. Code
+points not listed fold to themselves.
+
+Generated by `scripts/gen_tables.py`.""",
+        table(
+            "Code points that fold to a single, different code point, sorted.",
+            "SINGLE",
+            "&[(char, char)]",
+            [f"({char(cp)}, {char(to)})" for cp, (to,) in sorted(mappings["C"].items())],
+        ),
+        table(
+            "Code points that fold to more than one code point, sorted.",
+            "MULTI",
+            "&[(char, &str)]",
+            [f"({char(cp)}, {string(to)})" for cp, to in sorted(mappings["F"].items())],
+        ),
+        table(
+            "Code points in [`MULTI`] that simple case folding maps to a single,\n"
+            "different code point, sorted.",
+            "SIMPLE",
+            "&[(char, char)]",
+            [f"({char(cp)}, {char(to)})" for cp, (to,) in sorted(mappings["S"].items())],
+        ),
+    )
+
+
+def canonical(ucd_dir):
+    classes = {}
+    decompositions = {}
+
+    for fields in records(read(ucd_dir, UNICODE_DATA)):
+        cp = int(fields[0], 16)
+        if int(fields[3]):
+            classes[cp] = int(fields[3])
+        # Compatibility decompositions start with a `<tag>`.
+        if fields[5] and not fields[5].startswith("<"):
+            decompositions[cp] = [int(d, 16) for d in fields[5].split()]
+
+    def decompose(cp):
+        if cp not in decompositions:
+            return [cp]
+        return [d for part in decompositions[cp] for d in decompose(part)]
+
+    def reorder(cps):
+        """Put every run of non-starters in canonical order."""
+        cps = list(cps)
+        start = 0
+        for i in range(len(cps) + 1):
+            if i == len(cps) or classes.get(cps[i], 0) == 0:
+                cps[start:i] = sorted(cps[start:i], key=lambda cp: classes[cp])
+                start = i + 1
+        return cps
+
+    write(
+        "src/canonical/tables.rs",
+        f"""\
+Canonical decomposition tables for Unicode {UNICODE_VERSION}.
+
+Derived from `UnicodeData.txt`. Hangul syllables are decomposed
+algorithmically, and are not listed.
+
+Generated by `scripts/gen_tables.py`.""",
+        table(
+            "Code points and their full canonical decomposition, sorted.",
+            "DECOMPOSITIONS",
+            "&[(char, &str)]",
+            [
+                f"({char(cp)}, {string(reorder(decompose(cp)))})"
+                for cp in sorted(decompositions)
+            ],
+        ),
+        table(
+            "Sorted, non-overlapping ranges of code points with a non-zero\n"
+            "`Canonical_Combining_Class`.",
+            "COMBINING_CLASSES",
+            "&[(char, char, u8)]",
+            [f"({char(lo)}, {char(hi)}, {v})" for lo, hi, v in ranges(classes)],
+        ),
+    )
+
+
+def main():
+    ucd_dir = sys.argv[1] if len(sys.argv) > 1 else None
+
+    grapheme(ucd_dir)
+    casefold(ucd_dir)
+    canonical(ucd_dir)
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,262 @@
+// Small library to find a common prefix among strings.
+// Copyright (C) 2024  Sohum Mendon
+// SPDX-License-Identifier: MIT
+
+//! Running common prefixes of strings that arrive one at a time.
+//!
+//! [`LcpAccumulator`] copies the first string and shortens it in place, so
+//! the strings don't have to outlive it. [`BorrowedLcpAccumulator`] works
+//! without `alloc`, and borrows its prefix from the first string.
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+#[cfg(feature = "alloc")]
+use crate::common_prefix_len;
+use crate::longest_common_prefix;
+
+/// The running common prefix of the strings pushed so far, which owns its
+/// prefix.
+///
+/// ```rust
+/// use lcp::LcpAccumulator;
+///
+/// let mut acc = LcpAccumulator::new();
+///
+/// assert!(!acc.push("interstellar"));
+/// assert!(acc.push("internet"));
+/// assert!(!acc.push("interval"));
+/// assert_eq!(Some("inter"), acc.prefix());
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Clone, Default, Debug)]
+pub struct LcpAccumulator {
+    prefix: String,
+    /// Whether any strings were pushed, since `prefix` can be empty either
+    /// way.
+    seen: bool,
+}
+
+#[cfg(feature = "alloc")]
+impl LcpAccumulator {
+    /// Create an accumulator that hasn't seen any strings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a string, and return whether the prefix got shorter.
+    ///
+    /// The first string becomes the prefix, which doesn't count as
+    /// getting shorter.
+    pub fn push(&mut self, s: &str) -> bool {
+        if !self.seen {
+            self.prefix.push_str(s);
+            self.seen = true;
+            return false;
+        }
+
+        let len = common_prefix_len(&self.prefix, s);
+        let shrunk = len < self.prefix.len();
+
+        self.prefix.truncate(len);
+        shrunk
+    }
+
+    /// The common prefix of the strings pushed so far.
+    ///
+    /// This returns [`None`] if no strings were pushed. Otherwise, it
+    /// returns a [`str`] (including the empty string `""` if there is no
+    /// common prefix).
+    pub fn prefix(&self) -> Option<&str> {
+        self.seen.then_some(self.prefix.as_str())
+    }
+
+    /// The length of the prefix in bytes, which is zero if no strings
+    /// were pushed.
+    pub fn len(&self) -> usize {
+        self.prefix.len()
+    }
+
+    /// Whether the prefix is empty, or no strings were pushed.
+    pub fn is_empty(&self) -> bool {
+        self.prefix.is_empty()
+    }
+
+    /// Forget every string pushed so far.
+    ///
+    /// This keeps the allocation of the prefix for the next string.
+    pub fn reset(&mut self) {
+        self.prefix.clear();
+        self.seen = false;
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<S: AsRef<str>> Extend<S> for LcpAccumulator {
+    fn extend<I: IntoIterator<Item = S>>(&mut self, iter: I) {
+        for s in iter {
+            self.push(s.as_ref());
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<S: AsRef<str>> FromIterator<S> for LcpAccumulator {
+    fn from_iter<I: IntoIterator<Item = S>>(iter: I) -> Self {
+        let mut acc = Self::new();
+        acc.extend(iter);
+        acc
+    }
+}
+
+/// The running common prefix of the strings pushed so far, which borrows
+/// its prefix from the first one.
+///
+/// ```rust
+/// use lcp::BorrowedLcpAccumulator;
+///
+/// let acc: BorrowedLcpAccumulator = ["hello", "help", "hex"].into_iter().collect();
+///
+/// assert_eq!(Some("he"), acc.prefix());
+/// ```
+#[derive(Clone, Copy, Default, Debug)]
+pub struct BorrowedLcpAccumulator<'a> {
+    prefix: Option<&'a str>,
+}
+
+impl<'a> BorrowedLcpAccumulator<'a> {
+    /// Create an accumulator that hasn't seen any strings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a string, and return whether the prefix got shorter.
+    ///
+    /// The first string becomes the prefix, which doesn't count as
+    /// getting shorter.
+    pub fn push(&mut self, s: &'a str) -> bool {
+        match self.prefix {
+            None => {
+                self.prefix = Some(s);
+                false
+            }
+            Some(prefix) => {
+                let shorter = longest_common_prefix(prefix, s);
+
+                self.prefix = Some(shorter);
+                shorter.len() < prefix.len()
+            }
+        }
+    }
+
+    /// The common prefix of the strings pushed so far.
+    ///
+    /// This returns [`None`] if no strings were pushed. Otherwise, it
+    /// returns a [`str`] (including the empty string `""` if there is no
+    /// common prefix).
+    pub fn prefix(&self) -> Option<&'a str> {
+        self.prefix
+    }
+
+    /// The length of the prefix in bytes, which is zero if no strings
+    /// were pushed.
+    pub fn len(&self) -> usize {
+        self.prefix.map_or(0, str::len)
+    }
+
+    /// Whether the prefix is empty, or no strings were pushed.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Forget every string pushed so far.
+    pub fn reset(&mut self) {
+        self.prefix = None;
+    }
+}
+
+impl<'a, S: AsRef<str> + ?Sized + 'a> Extend<&'a S> for BorrowedLcpAccumulator<'a> {
+    fn extend<I: IntoIterator<Item = &'a S>>(&mut self, iter: I) {
+        for s in iter {
+            self.push(s.as_ref());
+        }
+    }
+}
+
+impl<'a, S: AsRef<str> + ?Sized + 'a> FromIterator<&'a S> for BorrowedLcpAccumulator<'a> {
+    fn from_iter<I: IntoIterator<Item = &'a S>>(iter: I) -> Self {
+        let mut acc = Self::new();
+        acc.extend(iter);
+        acc
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn borrowed() {
+        let mut acc = BorrowedLcpAccumulator::new();
+
+        assert_eq!(None, acc.prefix());
+        assert!(acc.is_empty());
+
+        assert!(!acc.push("h\u{E9}llo"));
+        assert_eq!(6, acc.len());
+        assert!(acc.push("h\u{E9}lp"));
+        assert!(!acc.push("h\u{E9}lpful"));
+        assert!(acc.push("h\u{E8}"));
+        assert_eq!(Some("h"), acc.prefix());
+
+        acc.reset();
+        assert_eq!(None, acc.prefix());
+
+        acc.extend(["abc", "abd"]);
+        assert_eq!(Some("ab"), acc.prefix());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn owned() {
+        let mut acc = LcpAccumulator::new();
+
+        for word in ["foo.rs", "foo.txt"] {
+            // The strings only live for one iteration.
+            acc.push(&String::from(word));
+        }
+
+        assert_eq!(Some("foo."), acc.prefix());
+        assert_eq!(4, acc.len());
+        assert!(acc.push("bar"));
+        assert!(!acc.push("baz"));
+        assert_eq!(Some(""), acc.prefix());
+        assert!(acc.is_empty());
+
+        acc.reset();
+        assert_eq!(None, acc.prefix());
+        assert!(!acc.push("again"));
+        assert_eq!(Some("again"), acc.prefix());
+    }
+
+    #[test]
+    fn empty_iterable() {
+        let iter: [&str; 0] = [];
+
+        assert_eq!(None, BorrowedLcpAccumulator::from_iter(iter).prefix());
+        #[cfg(feature = "alloc")]
+        assert_eq!(None, LcpAccumulator::from_iter(iter).prefix());
+    }
+
+    #[test]
+    fn common_prefix_in_iterable() {
+        let iter = ["interstellar", "internet", "interval"];
+
+        assert_eq!(
+            Some("inter"),
+            BorrowedLcpAccumulator::from_iter(iter).prefix()
+        );
+        #[cfg(feature = "alloc")]
+        assert_eq!(Some("inter"), LcpAccumulator::from_iter(iter).prefix());
+    }
+}
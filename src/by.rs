@@ -0,0 +1,188 @@
+// Small library to find a common prefix among strings.
+// Copyright (C) 2024  Sohum Mendon
+// SPDX-License-Identifier: MIT
+
+//! Common prefixes with a custom comparison for each `char`.
+
+/// Find the longest common prefix between two strings, comparing `char`s
+/// with `eq`.
+///
+/// The strings are compared `char` by `char`, and this returns a prefix
+/// of `a`, which can be the empty string `""` if there is no common prefix.
+///
+/// ```rust
+/// use lcp::longest_common_prefix_by;
+///
+/// let eq = |x: char, y: char| x == y || matches!((x, y), ('-', '_') | ('_', '-'));
+///
+/// let prefix = longest_common_prefix_by("my-crate-core", "my_crate_derive", eq);
+/// assert_eq!("my-crate-", prefix);
+/// ```
+pub fn longest_common_prefix_by<'a>(
+    a: &'a str,
+    b: &str,
+    mut eq: impl FnMut(char, char) -> bool,
+) -> &'a str {
+    let mut len = 0;
+
+    for ((i, x), y) in a.char_indices().zip(b.chars()) {
+        if !eq(x, y) {
+            break;
+        }
+
+        len = i + x.len_utf8();
+    }
+
+    &a[..len]
+}
+
+/// Find the longest prefix in an iterable, comparing `char`s with `eq`.
+///
+/// This returns [`None`] if the passed in iterable is empty. Otherwise,
+/// it returns a [`str`] (including the empty string `""` if there is
+/// no common prefix).
+pub fn longest_common_prefix_in_by<'a>(
+    iter: impl IntoIterator<Item = &'a str>,
+    mut eq: impl FnMut(char, char) -> bool,
+) -> Option<&'a str> {
+    let mut iter = iter.into_iter();
+
+    let mut lcp = iter.next()?;
+
+    for cur in iter {
+        lcp = longest_common_prefix_by(lcp, cur, &mut eq);
+
+        if lcp.is_empty() {
+            return Some(lcp);
+        }
+    }
+
+    Some(lcp)
+}
+
+/// Find the longest common prefix between two strings, comparing the key
+/// of each `char`.
+///
+/// This returns a prefix of `a`, which can be the empty string `""` if
+/// there is no common prefix.
+///
+/// ```rust
+/// use lcp::longest_common_prefix_by_key;
+///
+/// // Every decimal digit has the same key.
+/// let key = |c: char| if c.is_ascii_digit() { '0' } else { c };
+///
+/// let prefix = longest_common_prefix_by_key("v12.3-rc", "v45.6-beta", key);
+/// assert_eq!("v12.3-", prefix);
+/// ```
+pub fn longest_common_prefix_by_key<'a, K: PartialEq>(
+    a: &'a str,
+    b: &str,
+    mut key: impl FnMut(char) -> K,
+) -> &'a str {
+    longest_common_prefix_by(a, b, |x, y| key(x) == key(y))
+}
+
+/// Find the longest prefix in an iterable, comparing the key of each
+/// `char`.
+///
+/// This returns [`None`] if the passed in iterable is empty. Otherwise,
+/// it returns a [`str`] (including the empty string `""` if there is
+/// no common prefix).
+pub fn longest_common_prefix_in_by_key<'a, K: PartialEq>(
+    iter: impl IntoIterator<Item = &'a str>,
+    mut key: impl FnMut(char) -> K,
+) -> Option<&'a str> {
+    longest_common_prefix_in_by(iter, |x, y| key(x) == key(y))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_as_longest_common_prefix() {
+        let pairs = [
+            ("hello", "help"),
+            ("abc", "xyz"),
+            ("ab", "abc"),
+            ("abc", "ab"),
+            ("", "a"),
+            ("h\u{E9}llo", "h\u{E8}llo"),
+        ];
+
+        for (a, b) in pairs {
+            assert_eq!(
+                crate::longest_common_prefix(a, b),
+                longest_common_prefix_by(a, b, |x, y| x == y)
+            );
+        }
+    }
+
+    #[test]
+    fn custom_equality() {
+        let dash_is_underscore =
+            |x: char, y: char| x == y || matches!((x, y), ('-', '_') | ('_', '-'));
+
+        assert_eq!(
+            "foo-bar",
+            longest_common_prefix_by("foo-bar", "foo_bar", dash_is_underscore)
+        );
+        assert_eq!(
+            "HeLLo",
+            longest_common_prefix_by("HeLLo!", "hello?", |x: char, y: char| {
+                x.eq_ignore_ascii_case(&y)
+            })
+        );
+    }
+
+    #[test]
+    fn width_variants() {
+        // U+FF21 FULLWIDTH LATIN CAPITAL LETTER A and friends.
+        let narrow = |c: char| match c {
+            '\u{FF01}'..='\u{FF5E}' => char::from_u32(u32::from(c) - 0xFEE0).unwrap(),
+            _ => c,
+        };
+
+        assert_eq!(
+            "\u{FF21}\u{FF22}C",
+            longest_common_prefix_by_key("\u{FF21}\u{FF22}CD", "AB\u{FF23}E", narrow)
+        );
+    }
+
+    #[test]
+    fn keeps_chars_of_first() {
+        let key = |c: char| c.to_ascii_lowercase();
+
+        assert_eq!("ABC", longest_common_prefix_by_key("ABCD", "abcx", key));
+        assert_eq!("abc", longest_common_prefix_by_key("abcx", "ABCD", key));
+    }
+
+    #[test]
+    fn empty_iterable() {
+        let iter = [];
+
+        assert_eq!(None, longest_common_prefix_in_by(iter, |x, y| x == y));
+        assert_eq!(None, longest_common_prefix_in_by_key(iter, |c| c));
+    }
+
+    #[test]
+    fn common_prefix_in_iterable() {
+        let iter = ["v1.2.3", "v4.5.6", "v7.8"];
+        let key = |c: char| c.is_ascii_digit();
+
+        assert_eq!(Some("v1.2"), longest_common_prefix_in_by_key(iter, key));
+
+        let mut calls = 0;
+        let eq = |x: char, y: char| {
+            calls += 1;
+            x == y
+        };
+
+        assert_eq!(
+            Some("ab"),
+            longest_common_prefix_in_by(["abc", "abd", "abe"], eq)
+        );
+        assert_eq!(5, calls);
+    }
+}
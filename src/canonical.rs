@@ -0,0 +1,359 @@
+// Small library to find a common prefix among strings.
+// Copyright (C) 2024  Sohum Mendon
+// SPDX-License-Identifier: MIT
+
+//! Normalization-insensitive common prefixes.
+//!
+//! Strings are compared by their canonical decomposition (NFD), which is
+//! computed incrementally, so `"\u{E9}"` (NFC) matches `"e\u{301}"` (NFD).
+
+mod tables;
+
+use core::cmp::Ordering;
+use core::iter::Peekable;
+
+use crate::longest_common_prefix_with;
+use crate::normalize::{Normalizer, Unit};
+
+/// Find the longest common prefix between two strings, treating
+/// canonically equivalent sequences as equal.
+///
+/// This returns a prefix of `a`. The prefix never ends between a `char`
+/// and the combining marks that follow it.
+///
+/// ```rust
+/// use lcp::longest_common_prefix_canonical;
+///
+/// // NFC "é" against NFD "e" + U+0301 COMBINING ACUTE ACCENT.
+/// let prefix = longest_common_prefix_canonical("caf\u{E9}s", "cafe\u{301}!");
+/// assert_eq!("caf\u{E9}", prefix);
+/// ```
+pub fn longest_common_prefix_canonical<'a>(a: &'a str, b: &str) -> &'a str {
+    longest_common_prefix_with(&Nfc, a, b)
+}
+
+/// Find the longest prefix in an iterable, treating canonically
+/// equivalent sequences as equal.
+///
+/// This returns [`None`] if the passed in iterable is empty. Otherwise,
+/// it returns a prefix of the first string (including the empty string
+/// `""` if there is no common prefix).
+pub fn longest_common_prefix_canonical_in<'a>(
+    iter: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+    let mut iter = iter.into_iter();
+
+    let mut lcp = iter.next()?;
+
+    for cur in iter {
+        lcp = longest_common_prefix_canonical(lcp, cur);
+
+        if lcp.is_empty() {
+            return Some(lcp);
+        }
+    }
+
+    Some(lcp)
+}
+
+const HANGUL_S_BASE: u32 = 0xAC00;
+const HANGUL_L_BASE: u32 = 0x1100;
+const HANGUL_V_BASE: u32 = 0x1161;
+const HANGUL_T_BASE: u32 = 0x11A7;
+const HANGUL_T_COUNT: u32 = 28;
+const HANGUL_N_COUNT: u32 = 21 * HANGUL_T_COUNT;
+const HANGUL_S_COUNT: u32 = 19 * HANGUL_N_COUNT;
+
+/// The `Canonical_Combining_Class` of `c`.
+pub(crate) fn combining_class(c: char) -> u8 {
+    tables::COMBINING_CLASSES
+        .binary_search_by(|&(lo, hi, _)| {
+            if hi < c {
+                Ordering::Less
+            } else if lo > c {
+                Ordering::Greater
+            } else {
+                Ordering::Equal
+            }
+        })
+        .map_or(0, |i| tables::COMBINING_CLASSES[i].2)
+}
+
+/// Call `f` with each `char` of the full canonical decomposition of `c`.
+pub(crate) fn decompose(c: char, mut f: impl FnMut(char)) {
+    let s_index = u32::from(c).wrapping_sub(HANGUL_S_BASE);
+    if s_index < HANGUL_S_COUNT {
+        let lead = HANGUL_L_BASE + s_index / HANGUL_N_COUNT;
+        let vowel = HANGUL_V_BASE + (s_index % HANGUL_N_COUNT) / HANGUL_T_COUNT;
+        let trail = HANGUL_T_BASE + s_index % HANGUL_T_COUNT;
+
+        // Jamo are always valid `char`s.
+        f(char::from_u32(lead).unwrap());
+        f(char::from_u32(vowel).unwrap());
+        if trail != HANGUL_T_BASE {
+            f(char::from_u32(trail).unwrap());
+        }
+        return;
+    }
+
+    match tables::DECOMPOSITIONS.binary_search_by_key(&c, |&(from, _)| from) {
+        Ok(i) => tables::DECOMPOSITIONS[i].1.chars().for_each(f),
+        Err(_) => f(c),
+    }
+}
+
+/// Whether the decomposition of `c` starts with a starter, i.e. a `char`
+/// with a combining class of zero.
+fn starts_with_starter(c: char) -> bool {
+    let mut first = None;
+    decompose(c, |d| {
+        first.get_or_insert(d);
+    });
+
+    first.is_none_or(|d| combining_class(d) == 0)
+}
+
+/// Room for a starter and the combining marks after it. This is enough
+/// for any text in the Stream-Safe Text Format (UAX #15).
+const CAPACITY: usize = 32;
+
+/// Canonical equivalence, so `"\u{E9}"` (NFC) compares equal to
+/// `"e\u{301}"` (NFD).
+///
+/// The units are in canonically decomposed and reordered form, which is
+/// equal exactly when the NFC forms are.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct Nfc;
+
+impl Normalizer for Nfc {
+    type Output<I: Iterator<Item = Unit>> = Decomposed<I>;
+
+    fn normalize<I: Iterator<Item = Unit>>(&self, units: I) -> Decomposed<I> {
+        Decomposed {
+            source: units.peekable(),
+            buf: ['\0'; CAPACITY],
+            len: 0,
+            pos: 0,
+            span: (0, 0),
+            overflow: false,
+        }
+    }
+}
+
+/// The units of [`Nfc`].
+///
+/// The source is read in runs of a `char` and the combining marks after
+/// it, and every unit of a run has the span of the whole run.
+#[derive(Clone, Debug)]
+pub struct Decomposed<I: Iterator<Item = Unit>> {
+    source: Peekable<I>,
+    buf: [char; CAPACITY],
+    len: usize,
+    pos: usize,
+    /// The span of the buffered run.
+    span: (usize, usize),
+    /// Whether the buffered run didn't fit, and continues in the next one.
+    overflow: bool,
+}
+
+impl<I: Iterator<Item = Unit>> Decomposed<I> {
+    /// Buffer the next run of the source, and put it in canonical order.
+    fn fill(&mut self) -> bool {
+        self.len = 0;
+        self.pos = 0;
+
+        let Some(first) = self.source.next() else {
+            return false;
+        };
+
+        // A run that didn't fit keeps its start, so nothing lines up with
+        // the source until it is over.
+        if !self.overflow {
+            self.span.0 = first.start;
+        }
+        self.span.1 = first.end;
+        self.push(first.ch);
+
+        self.overflow = false;
+        while let Some(unit) = self.source.next_if(|unit| !starts_with_starter(unit.ch)) {
+            self.push(unit.ch);
+            self.span.1 = unit.end;
+
+            // Decompositions are at most four `char`s long.
+            if self.len + 4 > CAPACITY {
+                self.overflow = self
+                    .source
+                    .peek()
+                    .is_some_and(|unit| !starts_with_starter(unit.ch));
+                break;
+            }
+        }
+
+        // Stable insertion sort of each sequence of combining marks.
+        for i in 1..self.len {
+            let mut j = i;
+            while j > 0 {
+                let class = combining_class(self.buf[j]);
+                if class == 0 || combining_class(self.buf[j - 1]) <= class {
+                    break;
+                }
+
+                self.buf.swap(j - 1, j);
+                j -= 1;
+            }
+        }
+
+        true
+    }
+
+    fn push(&mut self, c: char) {
+        decompose(c, |d| {
+            self.buf[self.len] = d;
+            self.len += 1;
+        });
+    }
+}
+
+impl<I: Iterator<Item = Unit>> Iterator for Decomposed<I> {
+    type Item = Unit;
+
+    fn next(&mut self) -> Option<Unit> {
+        if self.pos == self.len && !self.fill() {
+            return None;
+        }
+
+        let ch = self.buf[self.pos];
+        self.pos += 1;
+
+        let (start, end) = self.span;
+        Some(Unit { ch, start, end })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ascii() {
+        assert_eq!("hel", longest_common_prefix_canonical("hello", "help"));
+        assert_eq!("", longest_common_prefix_canonical("abc", "xyz"));
+        assert_eq!("ab", longest_common_prefix_canonical("ab", "abc"));
+        assert_eq!("ab", longest_common_prefix_canonical("abc", "ab"));
+    }
+
+    #[test]
+    fn nfc_against_nfd() {
+        assert_eq!(
+            "\u{E9}",
+            longest_common_prefix_canonical("\u{E9}", "e\u{301}")
+        );
+        assert_eq!(
+            "e\u{301}",
+            longest_common_prefix_canonical("e\u{301}", "\u{E9}")
+        );
+        assert_eq!(
+            "r\u{E9}sum\u{E9}.",
+            longest_common_prefix_canonical("r\u{E9}sum\u{E9}.txt", "re\u{301}sume\u{301}.pdf")
+        );
+    }
+
+    #[test]
+    fn reordered_marks() {
+        // U+0323 DOT BELOW (220) sorts before U+0307 DOT ABOVE (230).
+        assert_eq!(
+            "q\u{307}\u{323}",
+            longest_common_prefix_canonical("q\u{307}\u{323}", "q\u{323}\u{307}")
+        );
+        // U+1E0B D WITH DOT ABOVE, plus DOT BELOW, is U+1E0D D WITH DOT
+        // BELOW, plus DOT ABOVE.
+        assert_eq!(
+            "\u{1E0B}\u{323}",
+            longest_common_prefix_canonical("\u{1E0B}\u{323}", "\u{1E0D}\u{307}")
+        );
+    }
+
+    #[test]
+    fn same_class_marks_keep_their_order() {
+        // Both marks have class 230, so they don't commute.
+        assert_eq!(
+            "",
+            longest_common_prefix_canonical("a\u{301}\u{300}", "a\u{300}\u{301}")
+        );
+    }
+
+    #[test]
+    fn prefix_does_not_split_marks() {
+        assert_eq!(
+            "caf",
+            longest_common_prefix_canonical("cafe\u{301}", "cafe")
+        );
+        assert_eq!("caf", longest_common_prefix_canonical("cafe", "caf\u{E9}"));
+        assert_eq!(
+            "caf",
+            longest_common_prefix_canonical("caf\u{E9}", "cafe\u{300}")
+        );
+    }
+
+    #[test]
+    fn hangul() {
+        // U+AC01 decomposes to L, V and T jamo.
+        assert_eq!(
+            "\u{AC01}",
+            longest_common_prefix_canonical("\u{AC01}", "\u{1100}\u{1161}\u{11A8}")
+        );
+        // U+AC00 is L and V, and is followed by a T jamo that makes it U+AC01.
+        assert_eq!(
+            "\u{AC00}\u{11A8}",
+            longest_common_prefix_canonical("\u{AC00}\u{11A8}", "\u{AC01}")
+        );
+        assert_eq!("", longest_common_prefix_canonical("\u{AC00}", "\u{AC01}"));
+    }
+
+    #[test]
+    fn singleton_decomposition() {
+        // U+212B ANGSTROM SIGN is canonically equivalent to U+00C5.
+        assert_eq!(
+            "\u{212B}",
+            longest_common_prefix_canonical("\u{212B}", "\u{C5}")
+        );
+        assert_eq!(
+            "\u{212B}",
+            longest_common_prefix_canonical("\u{212B}", "A\u{30A}")
+        );
+    }
+
+    #[test]
+    fn long_run_of_marks() {
+        // "a", then more combining marks than fit in one buffer, then "x".
+        let mut buf_a = [0u8; 202];
+        buf_a[0] = b'a';
+        for mark in buf_a[1..201].chunks_exact_mut(2) {
+            '\u{301}'.encode_utf8(mark);
+        }
+        buf_a[201] = b'x';
+
+        let mut buf_b = buf_a;
+        buf_b[201] = b'y';
+
+        let a = core::str::from_utf8(&buf_a).unwrap();
+        let b = core::str::from_utf8(&buf_b).unwrap();
+
+        assert_eq!(&a[..201], longest_common_prefix_canonical(a, b));
+        assert_eq!("", longest_common_prefix_canonical(a, &b[..199]));
+    }
+
+    #[test]
+    fn empty_iterable() {
+        let iter = [];
+
+        assert_eq!(None, longest_common_prefix_canonical_in(iter));
+    }
+
+    #[test]
+    fn common_prefix_in_iterable() {
+        let iter = ["caf\u{E9}-1", "cafe\u{301}-2", "caf\u{E9}-3"];
+
+        assert_eq!(Some("caf\u{E9}-"), longest_common_prefix_canonical_in(iter));
+    }
+}
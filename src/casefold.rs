@@ -0,0 +1,300 @@
+// Small library to find a common prefix among strings.
+// Copyright (C) 2024  Sohum Mendon
+// SPDX-License-Identifier: MIT
+
+//! Case-insensitive common prefixes.
+//!
+//! Strings are compared after full Unicode case folding, so `'ß'` matches
+//! `"ss"` and `'ﬃ'` matches `"ffi"`. [`SimpleCaseFold`] only maps each
+//! `char` to one `char`, for when that has to agree with other software.
+
+mod tables;
+
+use core::iter::Map;
+use core::str::Chars;
+
+use crate::longest_common_prefix_with;
+use crate::normalize::{Normalizer, Unit};
+
+/// Find the longest common prefix between two strings, ignoring case.
+///
+/// This returns a prefix of `a`, so the original casing of `a` is kept.
+/// A `char` that folds to several `char`s (like `'ß'` to `"ss"`) is only
+/// included if all of them matched.
+///
+/// ```rust
+/// use lcp::longest_common_prefix_ignore_case;
+///
+/// let prefix = longest_common_prefix_ignore_case("README.md", "readme.txt");
+/// assert_eq!("README.", prefix);
+///
+/// let prefix = longest_common_prefix_ignore_case("Straße", "STRASSE");
+/// assert_eq!("Straße", prefix);
+/// ```
+pub fn longest_common_prefix_ignore_case<'a>(a: &'a str, b: &str) -> &'a str {
+    longest_common_prefix_with(&CaseFold, a, b)
+}
+
+/// Find the longest prefix in an iterable, ignoring case.
+///
+/// This returns [`None`] if the passed in iterable is empty. Otherwise,
+/// it returns a prefix of the first string (including the empty string
+/// `""` if there is no common prefix).
+pub fn longest_common_prefix_ignore_case_in<'a>(
+    iter: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+    let mut iter = iter.into_iter();
+
+    let mut lcp = iter.next()?;
+
+    for cur in iter {
+        lcp = longest_common_prefix_ignore_case(lcp, cur);
+
+        if lcp.is_empty() {
+            return Some(lcp);
+        }
+    }
+
+    Some(lcp)
+}
+
+/// The case folding of a single `char`.
+pub(crate) enum Fold {
+    One(char),
+    Many(&'static str),
+}
+
+/// Look up the full case folding of `c`.
+pub(crate) fn fold(c: char) -> Fold {
+    if c.is_ascii() {
+        return Fold::One(c.to_ascii_lowercase());
+    }
+
+    if let Ok(i) = tables::SINGLE.binary_search_by_key(&c, |&(from, _)| from) {
+        return Fold::One(tables::SINGLE[i].1);
+    }
+
+    match tables::MULTI.binary_search_by_key(&c, |&(from, _)| from) {
+        Ok(i) => Fold::Many(tables::MULTI[i].1),
+        Err(_) => Fold::One(c),
+    }
+}
+
+/// Look up the simple case folding of `c`, which is always one `char`.
+pub(crate) fn fold_simple(c: char) -> char {
+    match fold(c) {
+        Fold::One(folded) => folded,
+        Fold::Many(_) => match tables::SIMPLE.binary_search_by_key(&c, |&(from, _)| from) {
+            Ok(i) => tables::SIMPLE[i].1,
+            Err(_) => c,
+        },
+    }
+}
+
+/// Full Unicode case folding, so `'ß'` compares equal to `"ss"`.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct CaseFold;
+
+impl Normalizer for CaseFold {
+    type Output<I: Iterator<Item = Unit>> = Folded<I>;
+
+    fn normalize<I: Iterator<Item = Unit>>(&self, units: I) -> Folded<I> {
+        Folded {
+            source: units,
+            pending: "".chars(),
+            span: (0, 0),
+        }
+    }
+}
+
+/// The units of [`CaseFold`].
+#[derive(Clone, Debug)]
+pub struct Folded<I> {
+    source: I,
+    pending: Chars<'static>,
+    /// The span of the unit that `pending` came from.
+    span: (usize, usize),
+}
+
+impl<I: Iterator<Item = Unit>> Iterator for Folded<I> {
+    type Item = Unit;
+
+    fn next(&mut self) -> Option<Unit> {
+        if let Some(ch) = self.pending.next() {
+            let (start, end) = self.span;
+            return Some(Unit { ch, start, end });
+        }
+
+        let unit = self.source.next()?;
+
+        match fold(unit.ch) {
+            Fold::One(ch) => Some(Unit { ch, ..unit }),
+            Fold::Many(folded) => {
+                self.pending = folded.chars();
+                self.span = (unit.start, unit.end);
+                self.next()
+            }
+        }
+    }
+}
+
+/// Simple Unicode case folding, which maps every `char` to one `char`.
+///
+/// Unlike [`CaseFold`], `'ß'` doesn't match `"ss"`, although it still
+/// matches `'ẞ'`.
+///
+/// ```rust
+/// use lcp::normalize::SimpleCaseFold;
+/// use lcp::longest_common_prefix_with;
+///
+/// assert_eq!("Stra", longest_common_prefix_with(&SimpleCaseFold, "Straße", "STRASSE"));
+/// assert_eq!("STRAẞE", longest_common_prefix_with(&SimpleCaseFold, "STRAẞE", "straße"));
+/// ```
+#[derive(Clone, Copy, Default, Debug)]
+pub struct SimpleCaseFold;
+
+impl Normalizer for SimpleCaseFold {
+    type Output<I: Iterator<Item = Unit>> = Map<I, fn(Unit) -> Unit>;
+
+    fn normalize<I: Iterator<Item = Unit>>(&self, units: I) -> Self::Output<I> {
+        units.map(|unit| Unit {
+            ch: fold_simple(unit.ch),
+            ..unit
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ascii() {
+        assert_eq!("HeLLo", longest_common_prefix_ignore_case("HeLLo", "hello"));
+        assert_eq!("hel", longest_common_prefix_ignore_case("hello", "HELP"));
+        assert_eq!("", longest_common_prefix_ignore_case("abc", "XYZ"));
+        assert_eq!("ab", longest_common_prefix_ignore_case("ab", "ABC"));
+        assert_eq!("ab", longest_common_prefix_ignore_case("abC", "ab"));
+    }
+
+    #[test]
+    fn keeps_casing_of_first() {
+        assert_eq!(
+            "README.",
+            longest_common_prefix_ignore_case("README.md", "readme.txt")
+        );
+        assert_eq!(
+            "readme.",
+            longest_common_prefix_ignore_case("readme.txt", "README.md")
+        );
+    }
+
+    #[test]
+    fn non_ascii() {
+        assert_eq!("ÉCOLE", longest_common_prefix_ignore_case("ÉCOLE", "école"));
+        assert_eq!(
+            "Σίσυφ",
+            longest_common_prefix_ignore_case("Σίσυφος", "σίσυφε")
+        );
+        // Final sigma folds like the other two sigmas.
+        assert_eq!("ς", longest_common_prefix_ignore_case("ς", "Σ"));
+        // KELVIN SIGN folds to a plain 'k'.
+        assert_eq!(
+            "\u{212A}m",
+            longest_common_prefix_ignore_case("\u{212A}m", "km")
+        );
+    }
+
+    #[test]
+    fn multi_char_folding() {
+        assert_eq!(
+            "Straße",
+            longest_common_prefix_ignore_case("Straße", "STRASSE")
+        );
+        assert_eq!(
+            "STRASSE",
+            longest_common_prefix_ignore_case("STRASSE", "straße")
+        );
+        assert_eq!("ß", longest_common_prefix_ignore_case("ß", "ẞ"));
+        assert_eq!(
+            "\u{FB03}x",
+            longest_common_prefix_ignore_case("\u{FB03}x", "FFIx")
+        );
+    }
+
+    #[test]
+    fn multi_char_folding_diverges_midway() {
+        // 'ß' folds to "ss", which only partly matches "sx".
+        assert_eq!("a", longest_common_prefix_ignore_case("aß", "asx"));
+        assert_eq!("a", longest_common_prefix_ignore_case("asx", "aß"));
+        // "s" alone is not a whole 'ß'.
+        assert_eq!("a", longest_common_prefix_ignore_case("aß", "as"));
+        assert_eq!("a", longest_common_prefix_ignore_case("as", "aß"));
+        // 'ﬃ' folds to "ffi"; "ﬀ" folds to "ff", so only "ff" lines up.
+        assert_eq!(
+            "",
+            longest_common_prefix_ignore_case("\u{FB03}", "\u{FB00}")
+        );
+        assert_eq!(
+            "",
+            longest_common_prefix_ignore_case("\u{FB03}", "\u{FB00}x")
+        );
+    }
+
+    #[test]
+    fn multi_char_folding_realigns() {
+        // "ﬀ" + "i" folds to the same "ffi" as 'ﬃ'.
+        assert_eq!(
+            "\u{FB03}",
+            longest_common_prefix_ignore_case("\u{FB03}!", "\u{FB00}i?")
+        );
+        assert_eq!(
+            "\u{FB00}i",
+            longest_common_prefix_ignore_case("\u{FB00}i?", "\u{FB03}!")
+        );
+        assert_eq!(
+            "\u{FB03}",
+            longest_common_prefix_ignore_case("\u{FB03}", "\u{FB00}I")
+        );
+    }
+
+    #[test]
+    fn simple_folding() {
+        let simple = |a, b| longest_common_prefix_with(&SimpleCaseFold, a, b);
+
+        assert_eq!("HeLLo", simple("HeLLo", "hello"));
+        assert_eq!("\u{212A}m", simple("\u{212A}m", "km"));
+        assert_eq!("ς", simple("ς", "Σ"));
+        // Without full folding, 'ß' and 'ﬃ' only match themselves.
+        assert_eq!("Stra", simple("Straße", "STRASSE"));
+        assert_eq!("", simple("\u{FB03}", "ffi"));
+        assert_eq!("ß", simple("ß", "ẞ"));
+        // These fold to several `char`s, but simple folding keeps one.
+        assert_eq!("\u{1F88}", simple("\u{1F88}", "\u{1F80}"));
+        assert_eq!("\u{1FD3}", simple("\u{1FD3}", "\u{390}"));
+        assert_eq!("\u{FB05}", simple("\u{FB05}", "\u{FB06}"));
+        assert_eq!("", simple("\u{130}", "i"));
+    }
+
+    #[test]
+    fn empty_iterable() {
+        let iter = [];
+
+        assert_eq!(None, longest_common_prefix_ignore_case_in(iter));
+    }
+
+    #[test]
+    fn common_prefix_in_iterable() {
+        let iter = ["README.md", "readme.txt", "ReadMe"];
+
+        assert_eq!(Some("README"), longest_common_prefix_ignore_case_in(iter));
+
+        let iter = ["Maße", "MASSE", "masse", "mass"];
+
+        assert_eq!(Some("Maß"), longest_common_prefix_ignore_case_in(iter));
+
+        let iter = ["Maße", "MASSE", "mas"];
+
+        assert_eq!(Some("Ma"), longest_common_prefix_ignore_case_in(iter));
+    }
+}
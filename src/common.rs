@@ -0,0 +1,452 @@
+// Small library to find a common prefix among strings.
+// Copyright (C) 2024  Sohum Mendon
+// SPDX-License-Identifier: MIT
+
+//! A [`CommonPrefix`] trait for everything that has prefixes.
+//!
+//! Each implementation measures prefixes in its own unit: bytes for
+//! [`str`] and `OsStr`, elements for slices, `char`s for [`Chars`],
+//! components for `Path`, and bits for integers and IP addresses.
+//! References and owning pointers have the prefixes of what they point
+//! to.
+
+use core::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use core::str::Chars;
+
+#[cfg(feature = "alloc")]
+use alloc::{
+    borrow::{Cow, ToOwned},
+    boxed::Box,
+    string::String,
+    vec::Vec,
+};
+#[cfg(feature = "std")]
+use std::{ffi::OsStr, path::Path};
+
+use crate::longest_common_prefix_slice;
+
+/// Types that have a longest common prefix with another value of the same
+/// type.
+///
+/// Only [`common_prefix_len`](Self::common_prefix_len) and
+/// [`prefix`](Self::prefix) have to be implemented.
+///
+/// ```rust
+/// use std::net::Ipv4Addr;
+///
+/// use lcp::CommonPrefix;
+///
+/// assert_eq!("hel", "hello".common_prefix("help"));
+/// assert_eq!(&[1, 2], [1, 2, 3].common_prefix(&[1, 2, 4]));
+///
+/// let (a, b) = (Ipv4Addr::new(10, 1, 2, 3), Ipv4Addr::new(10, 1, 3, 4));
+/// assert_eq!(23, a.common_prefix_len(&b));
+/// assert_eq!(Ipv4Addr::new(10, 1, 2, 0), a.common_prefix(&b));
+/// ```
+pub trait CommonPrefix {
+    /// The type of a prefix, such as `&str` for [`str`].
+    type Output<'a>
+    where
+        Self: 'a;
+
+    /// The length of the longest common prefix between `self` and `other`,
+    /// in the unit of this type.
+    fn common_prefix_len(&self, other: &Self) -> usize;
+
+    /// The first `len` units of `self`, or all of `self` if it is shorter.
+    ///
+    /// `len` should be the length of a common prefix of `self`, or larger.
+    fn prefix(&self, len: usize) -> Self::Output<'_>;
+
+    /// The longest common prefix between `self` and `other`, which is a
+    /// prefix of `self`.
+    fn common_prefix(&self, other: &Self) -> Self::Output<'_> {
+        self.prefix(self.common_prefix_len(other))
+    }
+}
+
+/// Find the longest prefix in an iterable of any [`CommonPrefix`] type.
+///
+/// This is the longest prefix of the first item that every other item
+/// shares. Unlike
+/// [`longest_common_prefix_in_as_ref`](crate::longest_common_prefix_in_as_ref),
+/// the items have to be references to the same type.
+///
+/// This returns [`None`] if the passed in iterable is empty. Otherwise,
+/// it returns a prefix of the first item (which is empty if there is no
+/// common prefix).
+///
+/// ```rust
+/// use std::net::Ipv4Addr;
+///
+/// use lcp::common_prefix_in;
+///
+/// assert_eq!(Some("inter"), common_prefix_in(["internet", "interval", "inter"]));
+///
+/// let addrs = [Ipv4Addr::new(192, 168, 1, 7), Ipv4Addr::new(192, 168, 2, 9)];
+/// assert_eq!(Some(Ipv4Addr::new(192, 168, 0, 0)), common_prefix_in(&addrs));
+/// ```
+pub fn common_prefix_in<'a, T>(iter: impl IntoIterator<Item = &'a T>) -> Option<T::Output<'a>>
+where
+    T: CommonPrefix + ?Sized + 'a,
+{
+    let mut iter = iter.into_iter();
+
+    let first = iter.next()?;
+    let mut len = usize::MAX;
+
+    // Every item shares the whole result with `first`, so the result is
+    // as long as the shortest prefix that an item shares with `first`.
+    for cur in iter {
+        len = len.min(first.common_prefix_len(cur));
+
+        if len == 0 {
+            break;
+        }
+    }
+
+    Some(first.prefix(len))
+}
+
+/// Prefixes in bytes, which never split a `char`.
+impl CommonPrefix for str {
+    type Output<'a> = &'a str;
+
+    fn common_prefix_len(&self, other: &str) -> usize {
+        crate::common_prefix_len(self, other)
+    }
+
+    fn prefix(&self, len: usize) -> &str {
+        &self[..len.min(self.len())]
+    }
+}
+
+/// Prefixes in elements. This includes byte strings, as `[u8]`, which
+/// [`longest_common_prefix_bytes`](crate::longest_common_prefix_bytes)
+/// compares faster.
+impl<T: PartialEq> CommonPrefix for [T] {
+    type Output<'a>
+        = &'a [T]
+    where
+        T: 'a;
+
+    fn common_prefix_len(&self, other: &[T]) -> usize {
+        longest_common_prefix_slice(self, other).len()
+    }
+
+    fn prefix(&self, len: usize) -> &[T] {
+        &self[..len.min(self.len())]
+    }
+}
+
+/// Prefixes in `char`s of the rest of the string.
+impl<'s> CommonPrefix for Chars<'s> {
+    type Output<'a>
+        = Chars<'s>
+    where
+        Self: 'a;
+
+    fn common_prefix_len(&self, other: &Self) -> usize {
+        self.clone()
+            .zip(other.clone())
+            .take_while(|(a, b)| a == b)
+            .count()
+    }
+
+    fn prefix(&self, len: usize) -> Chars<'s> {
+        let s = self.as_str();
+        let end = s.char_indices().nth(len).map_or(s.len(), |(i, _)| i);
+
+        s[..end].chars()
+    }
+}
+
+/// Prefixes in whole components, as in
+/// [`longest_common_std_path`](crate::longest_common_std_path).
+#[cfg(feature = "std")]
+impl CommonPrefix for Path {
+    type Output<'a> = &'a Path;
+
+    fn common_prefix_len(&self, other: &Path) -> usize {
+        crate::path::std_path::common_components(self, other)
+    }
+
+    fn prefix(&self, len: usize) -> &Path {
+        crate::path::std_path::ancestor(self, len)
+    }
+}
+
+/// Prefixes in bytes, as in
+/// [`longest_common_prefix_os`](crate::longest_common_prefix_os).
+#[cfg(feature = "std")]
+impl CommonPrefix for OsStr {
+    type Output<'a> = &'a OsStr;
+
+    fn common_prefix_len(&self, other: &OsStr) -> usize {
+        crate::longest_common_prefix_os(self, other).len()
+    }
+
+    fn prefix(&self, len: usize) -> &OsStr {
+        crate::os::prefix(self, len.min(self.len()))
+    }
+}
+
+/// Prefixes of the referenced value.
+impl<T: CommonPrefix + ?Sized> CommonPrefix for &T {
+    type Output<'a>
+        = T::Output<'a>
+    where
+        Self: 'a;
+
+    fn common_prefix_len(&self, other: &Self) -> usize {
+        (**self).common_prefix_len(other)
+    }
+
+    fn prefix(&self, len: usize) -> Self::Output<'_> {
+        (**self).prefix(len)
+    }
+}
+
+/// Prefixes of the [`str`] that is owned.
+#[cfg(feature = "alloc")]
+impl CommonPrefix for String {
+    type Output<'a> = &'a str;
+
+    fn common_prefix_len(&self, other: &String) -> usize {
+        self.as_str().common_prefix_len(other)
+    }
+
+    fn prefix(&self, len: usize) -> &str {
+        self.as_str().prefix(len)
+    }
+}
+
+/// Prefixes of the slice that is owned.
+#[cfg(feature = "alloc")]
+impl<T: PartialEq> CommonPrefix for Vec<T> {
+    type Output<'a>
+        = &'a [T]
+    where
+        T: 'a;
+
+    fn common_prefix_len(&self, other: &Vec<T>) -> usize {
+        self.as_slice().common_prefix_len(other)
+    }
+
+    fn prefix(&self, len: usize) -> &[T] {
+        self.as_slice().prefix(len)
+    }
+}
+
+/// Prefixes of the boxed value.
+#[cfg(feature = "alloc")]
+impl<T: CommonPrefix + ?Sized> CommonPrefix for Box<T> {
+    type Output<'a>
+        = T::Output<'a>
+    where
+        Self: 'a;
+
+    fn common_prefix_len(&self, other: &Self) -> usize {
+        (**self).common_prefix_len(other)
+    }
+
+    fn prefix(&self, len: usize) -> Self::Output<'_> {
+        (**self).prefix(len)
+    }
+}
+
+/// Prefixes of the borrowed or owned value.
+#[cfg(feature = "alloc")]
+impl<B: CommonPrefix + ToOwned + ?Sized> CommonPrefix for Cow<'_, B> {
+    type Output<'a>
+        = B::Output<'a>
+    where
+        Self: 'a;
+
+    fn common_prefix_len(&self, other: &Self) -> usize {
+        (**self).common_prefix_len(other)
+    }
+
+    fn prefix(&self, len: usize) -> Self::Output<'_> {
+        (**self).prefix(len)
+    }
+}
+
+macro_rules! impl_bits {
+    ($($int:ty),*) => {$(
+        /// Prefixes in bits, from the most significant bit. The prefix
+        /// keeps its place in the value, and the rest of the bits are
+        /// zero.
+        impl CommonPrefix for $int {
+            type Output<'a> = $int;
+
+            fn common_prefix_len(&self, other: &$int) -> usize {
+                (self ^ other).leading_zeros() as usize
+            }
+
+            fn prefix(&self, len: usize) -> $int {
+                if len >= <$int>::BITS as usize {
+                    *self
+                } else {
+                    self & !(<$int>::MAX >> len)
+                }
+            }
+        }
+    )*};
+}
+
+impl_bits!(u32, u64, u128);
+
+/// Prefixes in bits, so the prefix is the network address of the smallest
+/// subnet that contains both addresses.
+impl CommonPrefix for Ipv4Addr {
+    type Output<'a> = Ipv4Addr;
+
+    fn common_prefix_len(&self, other: &Ipv4Addr) -> usize {
+        self.to_bits().common_prefix_len(&other.to_bits())
+    }
+
+    fn prefix(&self, len: usize) -> Ipv4Addr {
+        Ipv4Addr::from_bits(self.to_bits().prefix(len))
+    }
+}
+
+/// Prefixes in bits, so the prefix is the network address of the smallest
+/// subnet that contains both addresses.
+impl CommonPrefix for Ipv6Addr {
+    type Output<'a> = Ipv6Addr;
+
+    fn common_prefix_len(&self, other: &Ipv6Addr) -> usize {
+        self.to_bits().common_prefix_len(&other.to_bits())
+    }
+
+    fn prefix(&self, len: usize) -> Ipv6Addr {
+        Ipv6Addr::from_bits(self.to_bits().prefix(len))
+    }
+}
+
+/// Prefixes in bits, as for [`Ipv4Addr`] and [`Ipv6Addr`]. Addresses of
+/// different families have no common prefix.
+impl CommonPrefix for IpAddr {
+    type Output<'a> = IpAddr;
+
+    fn common_prefix_len(&self, other: &IpAddr) -> usize {
+        match (self, other) {
+            (IpAddr::V4(a), IpAddr::V4(b)) => a.common_prefix_len(b),
+            (IpAddr::V6(a), IpAddr::V6(b)) => a.common_prefix_len(b),
+            _ => 0,
+        }
+    }
+
+    fn prefix(&self, len: usize) -> IpAddr {
+        match self {
+            IpAddr::V4(addr) => IpAddr::V4(addr.prefix(len)),
+            IpAddr::V6(addr) => IpAddr::V6(addr.prefix(len)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strings_and_slices() {
+        assert_eq!("h", "h\u{E9}".common_prefix("h\u{E8}"));
+        assert_eq!(1, "h\u{E9}".common_prefix_len("h\u{E8}"));
+
+        let (a, b): (&[u8], &[u8]) = (b"abc\xFF", b"abc\xFE");
+        assert_eq!(b"abc", a.common_prefix(b));
+        assert_eq!(&[1], [1, 2].common_prefix(&[1, 3, 4]));
+    }
+
+    #[test]
+    fn chars() {
+        let (a, b) = ("h\u{E9}llo".chars(), "h\u{E9}lp".chars());
+
+        assert_eq!(3, a.common_prefix_len(&b));
+        assert_eq!("h\u{E9}l", a.common_prefix(&b).as_str());
+    }
+
+    #[test]
+    fn bits() {
+        assert_eq!(32, 7_u32.common_prefix_len(&7));
+        assert_eq!(7, 7_u32.common_prefix(&7));
+        assert_eq!(0, u64::MAX.common_prefix_len(&0));
+        assert_eq!(0, u64::MAX.common_prefix(&0));
+        assert_eq!(127, 0b100_u128.common_prefix_len(&0b101));
+        assert_eq!(0b100, 0b101_u128.common_prefix(&0b100));
+    }
+
+    #[test]
+    fn ip_addrs() {
+        let a = Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1);
+        let b = Ipv6Addr::new(0x2001, 0xdb8, 0x8000, 0, 0, 0, 0, 1);
+
+        assert_eq!(32, a.common_prefix_len(&b));
+        assert_eq!(
+            Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0),
+            b.common_prefix(&a)
+        );
+
+        let v4 = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1));
+        let v6 = IpAddr::V6(a);
+
+        assert_eq!(0, v4.common_prefix_len(&v6));
+        assert_eq!(IpAddr::V4(Ipv4Addr::UNSPECIFIED), v4.common_prefix(&v6));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn paths_and_os_strs() {
+        let (a, b) = (Path::new("/usr/lib"), Path::new("/usr/libexec"));
+
+        assert_eq!(2, a.common_prefix_len(b));
+        assert_eq!(Path::new("/usr"), a.common_prefix(b));
+        assert_eq!(
+            OsStr::new("/usr/lib"),
+            a.as_os_str().common_prefix(b.as_os_str())
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn owned_and_borrowed() {
+        let strings = [String::from("prefix-a"), String::from("prefix-b")];
+
+        assert_eq!("prefix-", strings[0].common_prefix(&strings[1]));
+        assert_eq!(Some("prefix-"), common_prefix_in(&strings));
+
+        let cows = [Cow::Borrowed("abc"), Cow::Owned(String::from("abd"))];
+
+        assert_eq!(Some("ab"), common_prefix_in(&cows));
+
+        let boxes: [Box<[u8]>; 2] = [Box::new([1, 2]), Box::new([1, 3])];
+
+        assert_eq!(Some(&[1][..]), common_prefix_in(&boxes));
+        assert_eq!(Some("ab"), common_prefix_in(&["abc", "abd"]));
+    }
+
+    #[test]
+    fn empty_iterable() {
+        let iter: [&str; 0] = [];
+
+        assert_eq!(None, common_prefix_in(iter));
+    }
+
+    #[test]
+    fn common_prefix_in_iterable() {
+        let iter = ["h\u{E9}llo", "h\u{E9}lp", "h\u{E8}"];
+
+        assert_eq!(Some("h"), common_prefix_in(iter));
+
+        let iter: [&[i32]; 3] = [&[1, 2, 3], &[1, 2], &[1, 2, 4]];
+
+        assert_eq!(Some(&[1, 2][..]), common_prefix_in(iter));
+
+        let iter = [0xFF00_u32, 0xFF0F, 0xF0FF];
+
+        assert_eq!(Some(0xF000), common_prefix_in(&iter));
+        assert_eq!(Some(0xFF00), common_prefix_in(&iter[..1]));
+    }
+}
@@ -0,0 +1,141 @@
+// Small library to find a common prefix among strings.
+// Copyright (C) 2024  Sohum Mendon
+// SPDX-License-Identifier: MIT
+
+//! Common prefix lengths that can be found at compile time.
+//!
+//! These are `const fn`s, so they compare one byte at a time instead of
+//! using the faster kernel behind [`common_prefix_len`](crate::common_prefix_len).
+
+/// Find the length in bytes of the longest common prefix between two
+/// strings, in a `const` context.
+///
+/// This is always a `char` boundary in both strings, as in
+/// [`common_prefix_len`](crate::common_prefix_len).
+///
+/// ```rust
+/// use lcp::common_prefix_len_bytes;
+///
+/// const API: &str = "/api/v1/users";
+/// const ADMIN: &str = "/api/v1/admin";
+/// const PREFIX: &str = API.split_at(common_prefix_len_bytes(API, ADMIN)).0;
+///
+/// assert_eq!("/api/v1/", PREFIX);
+/// ```
+pub const fn common_prefix_len_bytes(a: &str, b: &str) -> usize {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+
+    let mut len = 0;
+
+    while len < a.len() && len < b.len() && a[len] == b[len] {
+        len += 1;
+    }
+
+    // Back off to a `char` boundary, as in `crate::common_prefix_len`.
+    while len < a.len() && is_continuation(a[len]) {
+        len -= 1;
+    }
+
+    len
+}
+
+/// Find the length in bytes of the longest prefix of a slice of strings,
+/// in a `const` context.
+///
+/// This returns [`None`] if the slice is empty. Otherwise, it returns a
+/// length that is a `char` boundary in every string (including zero if
+/// there is no common prefix).
+///
+/// ```rust
+/// use lcp::common_prefix_len_bytes_in;
+///
+/// const ROUTES: &[&str] = &["/api/v1/users", "/api/v1/admin", "/api/v2/status"];
+/// const LEN: usize = common_prefix_len_bytes_in(ROUTES).unwrap();
+///
+/// assert_eq!("/api/v", &ROUTES[0][..LEN]);
+/// ```
+pub const fn common_prefix_len_bytes_in(strings: &[&str]) -> Option<usize> {
+    let [first, rest @ ..] = strings else {
+        return None;
+    };
+
+    let mut len = first.len();
+    let mut i = 0;
+
+    while i < rest.len() && len > 0 {
+        let shared = common_prefix_len_bytes(first, rest[i]);
+
+        if shared < len {
+            len = shared;
+        }
+
+        i += 1;
+    }
+
+    Some(len)
+}
+
+/// Whether `byte` continues a UTF-8 `char`, instead of starting one.
+const fn is_continuation(byte: u8) -> bool {
+    byte & 0xC0 == 0x80
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::common_prefix_len;
+
+    const _: () = assert!(common_prefix_len_bytes("hello", "help") == 3);
+    const _: () = assert!(common_prefix_len_bytes("abc", "abc") == 3);
+    const _: () = assert!(common_prefix_len_bytes("", "abc") == 0);
+    // 'é' and 'è' share their first byte.
+    const _: () = assert!(common_prefix_len_bytes("h\u{E9}", "h\u{E8}") == 1);
+
+    #[test]
+    fn const_items() {
+        const A: &str = "caf\u{E9} \u{1F600}1";
+        const B: &str = "caf\u{E9} \u{1F601}2";
+        const LEN: usize = common_prefix_len_bytes(A, B);
+        const PREFIX: &str = A.split_at(LEN).0;
+
+        assert_eq!("caf\u{E9} ", PREFIX);
+    }
+
+    #[test]
+    fn same_as_common_prefix_len() {
+        let pairs = [
+            ("hello", "help"),
+            ("abc", "xyz"),
+            ("ab", "abc"),
+            ("abc", "ab"),
+            ("", ""),
+            ("h\u{E9}llo", "h\u{E9}lp"),
+            ("\u{1F600}", "\u{1F601}"),
+            ("\u{20AC}", "\u{20AD}"),
+        ];
+
+        for (a, b) in pairs {
+            assert_eq!(common_prefix_len(a, b), common_prefix_len_bytes(a, b));
+            assert_eq!(common_prefix_len(b, a), common_prefix_len_bytes(b, a));
+        }
+    }
+
+    #[test]
+    fn empty_iterable() {
+        const LEN: Option<usize> = common_prefix_len_bytes_in(&[]);
+
+        assert_eq!(None, LEN);
+    }
+
+    #[test]
+    fn common_prefix_in_iterable() {
+        const ROUTES: &[&str] = &["/caf\u{E9}/a", "/caf\u{E9}/b", "/caf\u{E8}"];
+        const LEN: Option<usize> = common_prefix_len_bytes_in(ROUTES);
+        const _: () = assert!(matches!(LEN, Some(4)));
+
+        assert_eq!(Some(4), LEN);
+        assert_eq!(Some(0), common_prefix_len_bytes_in(&["abc", "xyz", "abc"]));
+        assert_eq!(Some(3), common_prefix_len_bytes_in(&["abc"]));
+    }
+}
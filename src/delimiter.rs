@@ -0,0 +1,339 @@
+// Small library to find a common prefix among strings.
+// Copyright (C) 2024  Sohum Mendon
+// SPDX-License-Identifier: MIT
+
+//! Common prefixes that only end at delimiter boundaries.
+//!
+//! A prefix ends at a boundary if it is followed by a delimiter (or the end
+//! of the string) in both strings. Otherwise, it is cut back to the last
+//! delimiter inside it. A run of consecutive delimiters is cut as a whole.
+
+use crate::longest_common_prefix;
+
+/// A set of `char`s that separate tokens.
+///
+/// This is implemented for a single `char`, for arrays and slices of
+/// `char`s, and for predicates.
+///
+/// ```rust
+/// use lcp::longest_common_prefix_by_delimiter;
+///
+/// let a = "service.api.v1.users";
+/// let b = "service.api.v2.orders";
+///
+/// assert_eq!("service.api", longest_common_prefix_by_delimiter(a, b, '.'));
+/// assert_eq!("service.api", longest_common_prefix_by_delimiter(a, b, ['.', '/']));
+/// assert_eq!("service.api", longest_common_prefix_by_delimiter(a, b, |c: char| c.is_ascii_punctuation()));
+/// ```
+pub trait Delimiter {
+    /// Whether `c` is a delimiter.
+    fn is_delimiter(&mut self, c: char) -> bool;
+}
+
+impl Delimiter for char {
+    fn is_delimiter(&mut self, c: char) -> bool {
+        *self == c
+    }
+}
+
+impl Delimiter for &[char] {
+    fn is_delimiter(&mut self, c: char) -> bool {
+        self.contains(&c)
+    }
+}
+
+impl<const N: usize> Delimiter for [char; N] {
+    fn is_delimiter(&mut self, c: char) -> bool {
+        self.contains(&c)
+    }
+}
+
+impl<F: FnMut(char) -> bool> Delimiter for F {
+    fn is_delimiter(&mut self, c: char) -> bool {
+        self(c)
+    }
+}
+
+/// Find the longest common prefix between two strings that ends at a
+/// delimiter boundary, without the delimiter it ends at.
+///
+/// This returns a prefix of `a`, which can be the empty string `""` if no
+/// whole token is shared. The prefix never ends in a delimiter, even if
+/// several delimiters are in a row.
+///
+/// ```rust
+/// use lcp::longest_common_prefix_by_delimiter;
+///
+/// let prefix = longest_common_prefix_by_delimiter("a.b.c", "a.b.d", '.');
+/// assert_eq!("a.b", prefix);
+///
+/// let prefix = longest_common_prefix_by_delimiter("a.b", "a.bc", '.');
+/// assert_eq!("a", prefix);
+///
+/// let prefix = longest_common_prefix_by_delimiter("a..b", "a..c", '.');
+/// assert_eq!("a", prefix);
+/// ```
+pub fn longest_common_prefix_by_delimiter<'a>(
+    a: &'a str,
+    b: &str,
+    delims: impl Delimiter,
+) -> &'a str {
+    prefix_by_delimiter(a, b, delims, false)
+}
+
+/// Find the longest common prefix between two strings that ends at a
+/// delimiter boundary, keeping the delimiter it ends at.
+///
+/// This returns a prefix of `a`, which can be the empty string `""` if no
+/// whole token is shared.
+///
+/// ```rust
+/// use lcp::longest_common_prefix_by_delimiter_inclusive;
+///
+/// let prefix = longest_common_prefix_by_delimiter_inclusive("a.b.c", "a.b.d", '.');
+/// assert_eq!("a.b.", prefix);
+///
+/// // The delimiter after "a.b" is only in `a`, so it isn't kept.
+/// let prefix = longest_common_prefix_by_delimiter_inclusive("a.b.c", "a.b", '.');
+/// assert_eq!("a.b", prefix);
+/// ```
+pub fn longest_common_prefix_by_delimiter_inclusive<'a>(
+    a: &'a str,
+    b: &str,
+    delims: impl Delimiter,
+) -> &'a str {
+    prefix_by_delimiter(a, b, delims, true)
+}
+
+/// Find the longest prefix in an iterable that ends at a delimiter
+/// boundary, without the delimiter it ends at.
+///
+/// This returns [`None`] if the passed in iterable is empty. Otherwise,
+/// it returns a [`str`] (including the empty string `""` if there is
+/// no common prefix).
+pub fn longest_common_prefix_by_delimiter_in<'a>(
+    iter: impl IntoIterator<Item = &'a str>,
+    mut delims: impl Delimiter,
+) -> Option<&'a str> {
+    let mut iter = iter.into_iter();
+
+    let mut lcp = iter.next()?;
+
+    for cur in iter {
+        lcp = longest_common_prefix_by_delimiter(lcp, cur, |c| delims.is_delimiter(c));
+
+        if lcp.is_empty() {
+            return Some(lcp);
+        }
+    }
+
+    Some(lcp)
+}
+
+/// Find the longest prefix in an iterable that ends at a delimiter
+/// boundary, keeping the delimiter it ends at.
+///
+/// This returns [`None`] if the passed in iterable is empty. Otherwise,
+/// it returns a [`str`] (including the empty string `""` if there is
+/// no common prefix).
+pub fn longest_common_prefix_by_delimiter_inclusive_in<'a>(
+    iter: impl IntoIterator<Item = &'a str>,
+    mut delims: impl Delimiter,
+) -> Option<&'a str> {
+    let mut iter = iter.into_iter();
+
+    let mut lcp = iter.next()?;
+
+    for cur in iter {
+        lcp = longest_common_prefix_by_delimiter_inclusive(lcp, cur, |c| delims.is_delimiter(c));
+
+        if lcp.is_empty() {
+            return Some(lcp);
+        }
+    }
+
+    Some(lcp)
+}
+
+fn prefix_by_delimiter<'a>(
+    a: &'a str,
+    b: &str,
+    mut delims: impl Delimiter,
+    inclusive: bool,
+) -> &'a str {
+    let lcp = longest_common_prefix(a, b);
+    let len = lcp.len();
+
+    let mut at_boundary = |rest: &str| rest.chars().next().is_none_or(|c| delims.is_delimiter(c));
+
+    let cut = if at_boundary(&a[len..]) && at_boundary(&b[len..]) {
+        lcp
+    } else {
+        match lcp
+            .char_indices()
+            .rev()
+            .find(|&(_, c)| delims.is_delimiter(c))
+        {
+            Some((i, c)) => &a[..i + c.len_utf8()],
+            None => "",
+        }
+    };
+
+    if inclusive {
+        cut
+    } else {
+        cut.trim_end_matches(|c| delims.is_delimiter(c))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cuts_at_last_delimiter() {
+        let a = "service.api.v1.users";
+        let b = "service.api.v2.orders";
+
+        assert_eq!("service.api", longest_common_prefix_by_delimiter(a, b, '.'));
+        assert_eq!(
+            "service.api.",
+            longest_common_prefix_by_delimiter_inclusive(a, b, '.')
+        );
+    }
+
+    #[test]
+    fn whole_token() {
+        assert_eq!(
+            "a.b",
+            longest_common_prefix_by_delimiter("a.b", "a.b.c", '.')
+        );
+        assert_eq!(
+            "a.b",
+            longest_common_prefix_by_delimiter("a.b.c", "a.b", '.')
+        );
+        assert_eq!(
+            "a.b",
+            longest_common_prefix_by_delimiter_inclusive("a.b.c", "a.b", '.')
+        );
+        assert_eq!("a.b", longest_common_prefix_by_delimiter("a.b", "a.b", '.'));
+        assert_eq!("a", longest_common_prefix_by_delimiter("a.b", "a.bc", '.'));
+    }
+
+    #[test]
+    fn no_common_token() {
+        assert_eq!("", longest_common_prefix_by_delimiter("ab", "ac", '.'));
+        assert_eq!("", longest_common_prefix_by_delimiter("ab.c", "abc", '.'));
+        assert_eq!(
+            "",
+            longest_common_prefix_by_delimiter_inclusive("ab", "abc", '.')
+        );
+        assert_eq!("", longest_common_prefix_by_delimiter("", "a", '.'));
+    }
+
+    #[test]
+    fn leading_delimiter() {
+        assert_eq!("", longest_common_prefix_by_delimiter("/a", "/b", '/'));
+        assert_eq!(
+            "/",
+            longest_common_prefix_by_delimiter_inclusive("/a", "/b", '/')
+        );
+    }
+
+    #[test]
+    fn consecutive_delimiters() {
+        assert_eq!("a", longest_common_prefix_by_delimiter("a..b", "a..c", '.'));
+        assert_eq!(
+            "a..",
+            longest_common_prefix_by_delimiter_inclusive("a..b", "a..c", '.')
+        );
+        assert_eq!("a", longest_common_prefix_by_delimiter("a..", "a..", '.'));
+        assert_eq!(
+            "a",
+            longest_common_prefix_by_delimiter("a./.b", "a./.c", ['.', '/'])
+        );
+        assert_eq!("", longest_common_prefix_by_delimiter("..a", "..b", '.'));
+        assert_eq!(
+            Some("a"),
+            longest_common_prefix_by_delimiter_in(["a..b", "a..c", "a..d"], '.')
+        );
+    }
+
+    #[test]
+    fn delimiter_sets() {
+        let a = "src/lib.rs";
+        let b = "src/lib/mod.rs";
+
+        assert_eq!(
+            "src/lib",
+            longest_common_prefix_by_delimiter(a, b, ['/', '.'])
+        );
+        assert_eq!(
+            "src/lib",
+            longest_common_prefix_by_delimiter(a, b, &['/', '.'][..])
+        );
+        assert_eq!(
+            "src",
+            longest_common_prefix_by_delimiter(a, b, |c: char| c == '/')
+        );
+    }
+
+    #[test]
+    fn multibyte_delimiter() {
+        assert_eq!(
+            "a\u{2192}b\u{2192}",
+            longest_common_prefix_by_delimiter_inclusive(
+                "a\u{2192}b\u{2192}c",
+                "a\u{2192}b\u{2192}d",
+                '\u{2192}'
+            )
+        );
+    }
+
+    #[test]
+    fn empty_iterable() {
+        let iter = [];
+
+        assert_eq!(None, longest_common_prefix_by_delimiter_in(iter, '.'));
+    }
+
+    #[test]
+    fn common_prefix_in_iterable() {
+        let iter = ["a.b.x", "a.b.y", "a.bc"];
+
+        assert_eq!(Some("a"), longest_common_prefix_by_delimiter_in(iter, '.'));
+
+        let iter = ["a.b.x", "a.b.y", "a.b.z"];
+
+        assert_eq!(
+            Some("a.b"),
+            longest_common_prefix_by_delimiter_in(iter, '.')
+        );
+        assert_eq!(
+            Some("a.b."),
+            longest_common_prefix_by_delimiter_inclusive_in(iter, '.')
+        );
+
+        let iter = ["a.b.x", "a.b.y", "a.b"];
+
+        assert_eq!(
+            Some("a.b"),
+            longest_common_prefix_by_delimiter_inclusive_in(iter, '.')
+        );
+    }
+
+    #[test]
+    fn stateful_predicate() {
+        let mut calls = 0;
+        let delims = |c: char| {
+            calls += 1;
+            c == '.'
+        };
+
+        assert_eq!(
+            Some("a"),
+            longest_common_prefix_by_delimiter_in(["a.b", "a.c", "a.d"], delims)
+        );
+        assert!(calls > 0);
+    }
+}
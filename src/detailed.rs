@@ -0,0 +1,426 @@
+// Small library to find a common prefix among strings.
+// Copyright (C) 2024  Sohum Mendon
+// SPDX-License-Identifier: MIT
+
+//! Common prefixes with details about where and why they end.
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::grapheme::Segmenter;
+use crate::longest_common_prefix;
+
+/// A common prefix, with its sizes and the input that shortened it.
+///
+/// This is returned by [`longest_common_prefix_detailed`] and
+/// [`longest_common_prefix_detailed_in`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct LcpResult<'a> {
+    /// The common prefix, which is a prefix of the first input.
+    pub prefix: &'a str,
+    /// The length of the prefix in bytes.
+    pub len: usize,
+    /// The number of `char`s in the prefix.
+    pub chars: usize,
+    /// The number of extended grapheme clusters in the prefix.
+    pub graphemes: usize,
+    /// The index of the first input that shortened the prefix to its final
+    /// length, or [`None`] if the prefix is the whole first input.
+    pub shortened_by: Option<usize>,
+    /// The `char` right after the prefix in each input.
+    pub divergent_chars: DivergentChars,
+}
+
+impl<'a> LcpResult<'a> {
+    /// Describe `prefix`, which is a prefix of `first`.
+    fn new(
+        first: &'a str,
+        prefix: &'a str,
+        shortened_by: Option<usize>,
+        mut divergent_chars: DivergentChars,
+    ) -> Self {
+        let mut segmenter = Segmenter::new();
+
+        divergent_chars.prefix_len = prefix.len();
+        divergent_chars.first = first[prefix.len()..].chars().next();
+
+        LcpResult {
+            prefix,
+            len: prefix.len(),
+            chars: prefix.chars().count(),
+            graphemes: prefix
+                .chars()
+                .filter(|&c| segmenter.is_boundary_before(c))
+                .count(),
+            shortened_by,
+            divergent_chars,
+        }
+    }
+}
+
+/// How many inputs [`DivergentChars`] records without allocating.
+const INLINE_INPUTS: usize = 8;
+
+/// Where an input stopped matching the prefix it was compared against, and
+/// the `char` it has there.
+type Entry = (usize, Option<char>);
+
+/// The `char` right after the common prefix in each input of an
+/// [`LcpResult`], in the order of the inputs. It is [`None`] for inputs
+/// that end with the prefix.
+///
+/// The first 8 inputs are recorded inline. With the `alloc` feature, the
+/// rest are recorded on the heap. Without it, they are not recorded, and
+/// [`len`](Self::len) is less than the number of inputs.
+///
+/// ```rust
+/// use lcp::longest_common_prefix_detailed_in;
+///
+/// let result = longest_common_prefix_detailed_in(["hello", "help", "he"]).unwrap();
+/// let chars: Vec<_> = result.divergent_chars.iter().collect();
+///
+/// assert_eq!([Some('l'), Some('l'), None], chars[..]);
+/// ```
+#[derive(Clone)]
+pub struct DivergentChars {
+    /// The `char` after the final prefix in the first input.
+    first: Option<char>,
+    /// The length of the final prefix in bytes.
+    prefix_len: usize,
+    inline: [Entry; INLINE_INPUTS],
+    inline_len: usize,
+    #[cfg(feature = "alloc")]
+    spilled: Vec<Entry>,
+}
+
+impl DivergentChars {
+    fn new() -> Self {
+        DivergentChars {
+            first: None,
+            prefix_len: 0,
+            inline: [(0, None); INLINE_INPUTS],
+            inline_len: 0,
+            #[cfg(feature = "alloc")]
+            spilled: Vec::new(),
+        }
+    }
+
+    /// Record an input that matched the prefix so far up to byte `offset`,
+    /// where it has `c`.
+    fn push(&mut self, offset: usize, c: Option<char>) {
+        if self.inline_len < INLINE_INPUTS {
+            self.inline[self.inline_len] = (offset, c);
+            self.inline_len += 1;
+        } else {
+            #[cfg(feature = "alloc")]
+            self.spilled.push((offset, c));
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    fn spilled(&self) -> &[Entry] {
+        &self.spilled
+    }
+
+    #[cfg(not(feature = "alloc"))]
+    #[allow(clippy::unused_self)]
+    fn spilled(&self) -> &[Entry] {
+        &[]
+    }
+
+    /// An input that matched up to `offset` matches the whole final
+    /// prefix, so if it goes on past the prefix, it has the same `char` as
+    /// the first input there.
+    fn resolve(&self, (offset, c): Entry) -> Option<char> {
+        if offset > self.prefix_len {
+            self.first
+        } else {
+            c
+        }
+    }
+
+    /// The number of recorded inputs.
+    pub fn len(&self) -> usize {
+        self.inline_len + self.spilled().len()
+    }
+
+    /// Whether no inputs are recorded, which never happens in an
+    /// [`LcpResult`].
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The `char` right after the prefix in input `i`, or [`None`] if input
+    /// `i` isn't recorded.
+    pub fn get(&self, i: usize) -> Option<Option<char>> {
+        let entry = if i < self.inline_len {
+            self.inline[i]
+        } else {
+            *self.spilled().get(i.checked_sub(INLINE_INPUTS)?)?
+        };
+
+        Some(self.resolve(entry))
+    }
+
+    /// The `char` right after the prefix in each recorded input.
+    pub fn iter(&self) -> impl Iterator<Item = Option<char>> + '_ {
+        self.inline[..self.inline_len]
+            .iter()
+            .chain(self.spilled())
+            .map(|&entry| self.resolve(entry))
+    }
+}
+
+impl PartialEq for DivergentChars {
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+impl Eq for DivergentChars {}
+
+impl fmt::Debug for DivergentChars {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/// Find the longest common prefix between two strings, with details about
+/// it.
+///
+/// ```rust
+/// use lcp::longest_common_prefix_detailed;
+///
+/// let result = longest_common_prefix_detailed("cafe\u{301}s", "cafe\u{301}!");
+///
+/// assert_eq!("cafe\u{301}", result.prefix);
+/// assert_eq!(6, result.len);
+/// assert_eq!(5, result.chars);
+/// assert_eq!(4, result.graphemes);
+/// assert_eq!(Some(1), result.shortened_by);
+/// assert_eq!(Some(Some('s')), result.divergent_chars.get(0));
+/// assert_eq!(Some(Some('!')), result.divergent_chars.get(1));
+/// ```
+pub fn longest_common_prefix_detailed<'a>(a: &'a str, b: &str) -> LcpResult<'a> {
+    let prefix = longest_common_prefix(a, b);
+    let shortened_by = (prefix.len() < a.len()).then_some(1);
+
+    let mut divergent_chars = DivergentChars::new();
+    divergent_chars.push(a.len(), None);
+    divergent_chars.push(prefix.len(), b[prefix.len()..].chars().next());
+
+    LcpResult::new(a, prefix, shortened_by, divergent_chars)
+}
+
+/// Find the longest prefix in an iterable, with details about it.
+///
+/// This returns [`None`] if the passed in iterable is empty. Otherwise, it
+/// returns an [`LcpResult`], whose prefix can be the empty string `""` if
+/// there is no common prefix. Inputs are counted from zero, so
+/// [`LcpResult::shortened_by`] is never `Some(0)`.
+///
+/// Every input is only looked at once, so this works with iterators that
+/// can't be restarted.
+///
+/// ```rust
+/// use lcp::longest_common_prefix_detailed_in;
+///
+/// let result = longest_common_prefix_detailed_in(["hello", "help", "hex", "hexagon"]).unwrap();
+/// let chars: Vec<_> = result.divergent_chars.iter().flatten().collect();
+///
+/// assert_eq!("he", result.prefix);
+/// assert_eq!(Some(2), result.shortened_by);
+/// assert_eq!(['l', 'l', 'x', 'x'], chars[..]);
+/// ```
+pub fn longest_common_prefix_detailed_in<'a>(
+    iter: impl IntoIterator<Item = &'a str>,
+) -> Option<LcpResult<'a>> {
+    let mut iter = iter.into_iter();
+
+    let first = iter.next()?;
+    let mut lcp = first;
+    let mut shortened_by = None;
+
+    let mut divergent_chars = DivergentChars::new();
+    divergent_chars.push(first.len(), None);
+
+    // This goes on after the prefix is empty, to record every input.
+    for (i, cur) in (1..).zip(iter) {
+        let shorter = longest_common_prefix(lcp, cur);
+
+        if shorter.len() < lcp.len() {
+            lcp = shorter;
+            shortened_by = Some(i);
+        }
+
+        divergent_chars.push(shorter.len(), cur[shorter.len()..].chars().next());
+    }
+
+    Some(LcpResult::new(first, lcp, shortened_by, divergent_chars))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts() {
+        let result = longest_common_prefix_detailed("h\u{E9}llo", "h\u{E9}lp");
+
+        assert_eq!("h\u{E9}l", result.prefix);
+        assert_eq!(4, result.len);
+        assert_eq!(3, result.chars);
+        assert_eq!(3, result.graphemes);
+
+        // MAN, ZWJ, WOMAN is one grapheme cluster.
+        let family = "\u{1F468}\u{200D}\u{1F469}";
+        let result = longest_common_prefix_detailed(family, family);
+
+        assert_eq!(3, result.chars);
+        assert_eq!(1, result.graphemes);
+    }
+
+    #[test]
+    fn shortened_by() {
+        assert_eq!(
+            Some(1),
+            longest_common_prefix_detailed("abc", "abd").shortened_by
+        );
+        assert_eq!(
+            Some(1),
+            longest_common_prefix_detailed("abc", "ab").shortened_by
+        );
+        assert_eq!(
+            None,
+            longest_common_prefix_detailed("ab", "abc").shortened_by
+        );
+        assert_eq!(
+            None,
+            longest_common_prefix_detailed("abc", "abc").shortened_by
+        );
+    }
+
+    #[test]
+    fn divergent_chars() {
+        let chars = |a, b| {
+            let result = longest_common_prefix_detailed(a, b);
+            (result.divergent_chars.get(0), result.divergent_chars.get(1))
+        };
+
+        assert_eq!((Some(Some('e')), Some(None)), chars("flower", "flow"));
+        assert_eq!(
+            (Some(Some('\u{E9}')), Some(Some('\u{E8}'))),
+            chars("h\u{E9}", "h\u{E8}")
+        );
+        assert_eq!((Some(None), Some(Some('e'))), chars("flow", "flower"));
+        assert_eq!((Some(None), Some(None)), chars("flow", "flow"));
+    }
+
+    #[test]
+    fn divergent_chars_of_every_input() {
+        let iter = ["hello", "help", "hex", "hexagon", "he"];
+        let result = longest_common_prefix_detailed_in(iter).unwrap();
+        let chars = [Some('l'), Some('l'), Some('x'), Some('x'), None];
+
+        assert_eq!(chars.len(), result.divergent_chars.len());
+        assert!(result.divergent_chars.iter().eq(chars));
+        assert_eq!(None, result.divergent_chars.get(chars.len()));
+    }
+
+    #[test]
+    fn divergent_chars_past_inline_inputs() {
+        let iter = ["ab", "ac"].repeat(10);
+        let result = longest_common_prefix_detailed_in(iter.iter().copied()).unwrap();
+
+        let recorded = if cfg!(feature = "alloc") {
+            iter.len()
+        } else {
+            INLINE_INPUTS
+        };
+
+        assert_eq!(recorded, result.divergent_chars.len());
+        assert_eq!(Some(Some('b')), result.divergent_chars.get(0));
+        assert_eq!(Some(Some('c')), result.divergent_chars.get(7));
+        assert_eq!(
+            cfg!(feature = "alloc").then_some(Some('c')),
+            result.divergent_chars.get(19)
+        );
+    }
+
+    #[test]
+    fn one_shot_iterator() {
+        let iter = "tea ten toe".split(' ');
+        let result = longest_common_prefix_detailed_in(iter).unwrap();
+
+        assert_eq!("t", result.prefix);
+        assert_eq!(Some(2), result.shortened_by);
+        assert!(result
+            .divergent_chars
+            .iter()
+            .eq([Some('e'), Some('e'), Some('o')]));
+    }
+
+    #[test]
+    fn empty_iterable() {
+        let iter = [];
+
+        assert_eq!(None, longest_common_prefix_detailed_in(iter));
+    }
+
+    #[test]
+    fn common_prefix_in_iterable() {
+        let iter = ["interstellar", "internet", "interval", "inter", "in"];
+        let result = longest_common_prefix_detailed_in(iter).unwrap();
+
+        assert_eq!("in", result.prefix);
+        assert_eq!(2, result.chars);
+        assert_eq!(Some(4), result.shortened_by);
+
+        let iter = ["interstellar", "internet", "interval", "inter"];
+        let result = longest_common_prefix_detailed_in(iter).unwrap();
+
+        assert_eq!("inter", result.prefix);
+        assert_eq!(Some(1), result.shortened_by);
+        assert!(result
+            .divergent_chars
+            .iter()
+            .eq([Some('s'), Some('n'), Some('v'), None]));
+
+        let iter = ["same", "same", "same"];
+
+        assert_eq!(
+            None,
+            longest_common_prefix_detailed_in(iter)
+                .unwrap()
+                .shortened_by
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn common_prefix_in_strings() {
+        use alloc::string::String;
+
+        let iter = [String::from("hello"), String::from("help")];
+        let result = longest_common_prefix_detailed_in(iter.iter().map(String::as_str)).unwrap();
+
+        assert_eq!("hel", result.prefix);
+        assert_eq!(Some(1), result.shortened_by);
+        assert_eq!(Some(Some('p')), result.divergent_chars.get(1));
+    }
+
+    #[test]
+    fn empty_prefix() {
+        let iter = ["abc", "xyz", "abc"];
+        let result = longest_common_prefix_detailed_in(iter).unwrap();
+
+        assert_eq!("", result.prefix);
+        assert_eq!(Some(1), result.shortened_by);
+        assert!(result
+            .divergent_chars
+            .iter()
+            .eq([Some('a'), Some('x'), Some('a')]));
+        assert_eq!(0, result.graphemes);
+    }
+}
@@ -0,0 +1,232 @@
+// Small library to find a common prefix among strings.
+// Copyright (C) 2024  Sohum Mendon
+// SPDX-License-Identifier: MIT
+
+//! An extension trait to find common prefixes and suffixes of iterators.
+
+use crate::{
+    longest_common_prefix_bytes_in, longest_common_prefix_in_as_ref, longest_common_prefix_in_by,
+    longest_common_prefix_slice_in, longest_common_suffix_in,
+};
+
+/// Methods to find the common prefix or suffix of the items of an
+/// iterator.
+///
+/// This is implemented for every [`Iterator`], but each method is only
+/// available when the items are references to a suitable type. The string
+/// methods take references to anything that is [`AsRef<str>`], such as
+/// [`str`], `&str`, `String` or `Rc<str>`.
+///
+/// ```rust
+/// use lcp::LcpExt;
+///
+/// let names = ["report-2024.txt", "report-2023.txt"];
+///
+/// assert_eq!(Some("report-202"), names.iter().longest_common_prefix());
+/// assert_eq!(Some(".txt"), names.iter().longest_common_suffix());
+/// ```
+pub trait LcpExt: Iterator + Sized {
+    /// Find the longest prefix of the items, as in
+    /// [`longest_common_prefix_in_as_ref`].
+    ///
+    /// This returns [`None`] if the iterator is empty. Otherwise, it
+    /// returns a [`str`] (including the empty string `""` if there is no
+    /// common prefix).
+    ///
+    /// ```rust
+    /// use lcp::LcpExt;
+    ///
+    /// assert_eq!(Some("he"), ["hello", "help", "hex"].into_iter().longest_common_prefix());
+    /// ```
+    fn longest_common_prefix<'a, S>(self) -> Option<&'a str>
+    where
+        Self: Iterator<Item = &'a S>,
+        S: AsRef<str> + ?Sized + 'a,
+    {
+        longest_common_prefix_in_as_ref(self)
+    }
+
+    /// Find the longest suffix of the items, as in
+    /// [`longest_common_suffix_in`].
+    ///
+    /// This returns [`None`] if the iterator is empty. Otherwise, it
+    /// returns a [`str`] (including the empty string `""` if there is no
+    /// common suffix).
+    fn longest_common_suffix<'a, S>(self) -> Option<&'a str>
+    where
+        Self: Iterator<Item = &'a S>,
+        S: AsRef<str> + ?Sized + 'a,
+    {
+        longest_common_suffix_in(self.map(AsRef::as_ref))
+    }
+
+    /// Find the longest prefix of the items, comparing `char`s with `eq`,
+    /// as in [`longest_common_prefix_in_by`].
+    ///
+    /// This returns [`None`] if the iterator is empty. Otherwise, it
+    /// returns a [`str`] (including the empty string `""` if there is no
+    /// common prefix).
+    ///
+    /// ```rust
+    /// use lcp::LcpExt;
+    ///
+    /// let prefix = ["Hello", "HELP"]
+    ///     .into_iter()
+    ///     .common_prefix_by(|x, y| x.eq_ignore_ascii_case(&y));
+    ///
+    /// assert_eq!(Some("Hel"), prefix);
+    /// ```
+    fn common_prefix_by<'a, S>(self, eq: impl FnMut(char, char) -> bool) -> Option<&'a str>
+    where
+        Self: Iterator<Item = &'a S>,
+        S: AsRef<str> + ?Sized + 'a,
+    {
+        longest_common_prefix_in_by(self.map(AsRef::as_ref), eq)
+    }
+
+    /// Find the longest prefix of the items, as in
+    /// [`longest_common_prefix_slice_in`].
+    ///
+    /// The items can be references to anything that is [`AsRef<[T]>`],
+    /// such as `[T]`, `&[T]`, `[T; N]` or `Vec<T>`.
+    ///
+    /// This returns [`None`] if the iterator is empty. Otherwise, it
+    /// returns a slice (which is empty if there is no common prefix).
+    ///
+    /// ```rust
+    /// use lcp::LcpExt;
+    ///
+    /// let rows: [&[u8]; 2] = [&[1, 2, 3], &[1, 2, 4]];
+    /// assert_eq!(Some(&[1, 2][..]), rows.iter().longest_common_prefix_slice());
+    /// ```
+    fn longest_common_prefix_slice<'a, S, T>(self) -> Option<&'a [T]>
+    where
+        Self: Iterator<Item = &'a S>,
+        S: AsRef<[T]> + ?Sized + 'a,
+        T: PartialEq + 'a,
+    {
+        longest_common_prefix_slice_in(self.map(AsRef::as_ref))
+    }
+
+    /// Find the longest prefix of the items, as in
+    /// [`longest_common_prefix_bytes_in`].
+    ///
+    /// The items can be references to anything that is [`AsRef<[u8]>`],
+    /// such as `[u8]`, `&[u8]`, [`str`] or `Vec<u8>`.
+    ///
+    /// This returns [`None`] if the iterator is empty. Otherwise, it
+    /// returns a slice (which is empty if there is no common prefix).
+    ///
+    /// ```rust
+    /// use lcp::LcpExt;
+    ///
+    /// let keys: [&[u8]; 2] = [b"key\xFF\x01", b"key\xFF\x02"];
+    /// assert_eq!(Some(&b"key\xFF"[..]), keys.iter().longest_common_prefix_bytes());
+    /// ```
+    fn longest_common_prefix_bytes<'a, S>(self) -> Option<&'a [u8]>
+    where
+        Self: Iterator<Item = &'a S>,
+        S: AsRef<[u8]> + ?Sized + 'a,
+    {
+        longest_common_prefix_bytes_in(self.map(AsRef::as_ref))
+    }
+}
+
+impl<I: Iterator> LcpExt for I {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn borrowed_strings() {
+        let words = ["interstellar", "internet", "interval"];
+
+        assert_eq!(Some("inter"), words.iter().longest_common_prefix());
+        assert_eq!(Some("inter"), words.into_iter().longest_common_prefix());
+        assert_eq!(Some(""), words.iter().longest_common_suffix());
+
+        let words = ["Interstellar", "INTERNET"];
+
+        assert_eq!(
+            Some("Inter"),
+            words
+                .iter()
+                .common_prefix_by(|x, y| x.eq_ignore_ascii_case(&y))
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn owned_strings() {
+        use alloc::string::String;
+        use alloc::vec::Vec;
+
+        let words = [String::from("foo.rs"), String::from("foo.txt")];
+
+        assert_eq!(Some("foo."), words.iter().longest_common_prefix());
+        assert_eq!(Some(""), words.iter().longest_common_suffix());
+
+        let refs: Vec<&str> = words.iter().map(String::as_str).collect();
+
+        assert_eq!(Some("foo."), refs.iter().longest_common_prefix());
+        assert_eq!(Some("foo."), refs.iter().common_prefix_by(|x, y| x == y));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn shared_strings() {
+        use alloc::rc::Rc;
+        use alloc::vec::Vec;
+
+        let words: Vec<Rc<str>> = ["foo.rs", "foo.txt"].map(Rc::from).into();
+
+        assert_eq!(Some("foo."), words.iter().longest_common_prefix());
+        assert_eq!(Some(""), words.iter().longest_common_suffix());
+        assert_eq!(Some("foo."), words.iter().common_prefix_by(|x, y| x == y));
+    }
+
+    #[test]
+    fn slices() {
+        let rows: [&[i32]; 3] = [&[1, 2, 3], &[1, 2], &[1, 2, 4]];
+
+        assert_eq!(Some(&[1, 2][..]), rows.iter().longest_common_prefix_slice());
+
+        let rows = [[1, 2, 3], [1, 2, 4]];
+
+        assert_eq!(Some(&[1, 2][..]), rows.iter().longest_common_prefix_slice());
+        assert_eq!(Some(&[1, 2][..]), rows.iter().longest_common_prefix_bytes());
+
+        let words = ["h\u{E9}", "h\u{E8}"];
+
+        assert_eq!(
+            Some(&b"h\xC3"[..]),
+            words.iter().longest_common_prefix_bytes()
+        );
+    }
+
+    #[test]
+    fn empty_iterable() {
+        let iter: [&str; 0] = [];
+
+        assert_eq!(None, iter.iter().longest_common_prefix());
+        assert_eq!(None, iter.iter().longest_common_suffix());
+        assert_eq!(None, iter.iter().common_prefix_by(|x, y| x == y));
+
+        let iter: [&[u8]; 0] = [];
+
+        assert_eq!(None, iter.iter().longest_common_prefix_slice());
+        assert_eq!(None, iter.iter().longest_common_prefix_bytes());
+    }
+
+    #[test]
+    fn common_prefix_in_iterable() {
+        let iter = ["config.toml", "config.yaml", "config.json"];
+
+        assert_eq!(Some("config."), iter.iter().longest_common_prefix());
+        assert_eq!(
+            Some("config.toml"),
+            iter.iter().take(1).longest_common_prefix()
+        );
+    }
+}
@@ -0,0 +1,359 @@
+// Small library to find a common prefix among strings.
+// Copyright (C) 2024  Sohum Mendon
+// SPDX-License-Identifier: MIT
+
+//! Common prefixes that end on extended grapheme cluster boundaries.
+//!
+//! Boundaries follow the rules of [UAX #29], using the tables in
+//! [`tables`].
+//!
+//! [UAX #29]: https://www.unicode.org/reports/tr29/#Grapheme_Cluster_Boundary_Rules
+
+mod tables;
+
+use core::cmp::Ordering;
+
+use crate::longest_common_prefix;
+
+/// Find the longest common prefix between two strings that ends on an
+/// extended grapheme cluster boundary in both of them.
+///
+/// Unlike [`longest_common_prefix`], this never separates a base letter
+/// from its combining marks, or splits up an emoji sequence.
+///
+/// ```rust
+/// use lcp::longest_common_prefix_graphemes;
+///
+/// // "e" followed by U+0301 COMBINING ACUTE ACCENT.
+/// let prefix = longest_common_prefix_graphemes("cafe\u{301}", "cafe");
+/// assert_eq!("caf", prefix);
+/// ```
+pub fn longest_common_prefix_graphemes<'a>(a: &'a str, b: &str) -> &'a str {
+    let len = longest_common_prefix(a, b).len();
+
+    let mut segmenter = Segmenter::new();
+    let mut boundary = 0;
+
+    for (i, c) in a[..len].char_indices() {
+        if segmenter.is_boundary_before(c) {
+            boundary = i;
+        }
+    }
+
+    if segmenter.is_boundary_at(&a[len..]) && segmenter.is_boundary_at(&b[len..]) {
+        boundary = len;
+    }
+
+    &a[..boundary]
+}
+
+/// Find the longest prefix in an iterable that ends on an extended
+/// grapheme cluster boundary in every string.
+///
+/// This returns [`None`] if the passed in iterable is empty. Otherwise,
+/// it returns a [`str`] (including the empty string `""` if there is
+/// no common prefix).
+pub fn longest_common_prefix_graphemes_in<'a>(
+    iter: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+    let mut iter = iter.into_iter();
+
+    let mut lcp = iter.next()?;
+
+    for cur in iter {
+        lcp = longest_common_prefix_graphemes(lcp, cur);
+
+        if lcp.is_empty() {
+            return Some(lcp);
+        }
+    }
+
+    Some(lcp)
+}
+
+/// The `Grapheme_Cluster_Break` property, with `Extended_Pictographic` and
+/// `Indic_Conjunct_Break=Consonant` (which are both `Other`) split out.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[allow(clippy::upper_case_acronyms)]
+enum Category {
+    Other,
+    CR,
+    LF,
+    Control,
+    Extend,
+    ZWJ,
+    RegionalIndicator,
+    Prepend,
+    SpacingMark,
+    L,
+    V,
+    T,
+    LV,
+    LVT,
+    ExtendedPictographic,
+    InCbConsonant,
+}
+
+impl Category {
+    fn of(c: char) -> Self {
+        tables::CATEGORIES
+            .binary_search_by(|&(lo, hi, _)| {
+                if hi < c {
+                    Ordering::Less
+                } else if lo > c {
+                    Ordering::Greater
+                } else {
+                    Ordering::Equal
+                }
+            })
+            .map_or(Category::Other, |i| tables::CATEGORIES[i].2)
+    }
+}
+
+fn is_incb_linker(c: char) -> bool {
+    tables::INCB_LINKER.contains(&c)
+}
+
+fn is_incb_extend(c: char) -> bool {
+    tables::INCB_EXTEND
+        .binary_search_by(|&(lo, hi)| {
+            if hi < c {
+                Ordering::Less
+            } else if lo > c {
+                Ordering::Greater
+            } else {
+                Ordering::Equal
+            }
+        })
+        .is_ok()
+}
+
+/// Progress through the sequence in rule `GB9c`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Conjunct {
+    None,
+    /// An `InCB=Consonant`, then `InCB=Extend`s.
+    Consonant,
+    /// An `InCB=Consonant`, then at least one `InCB=Linker`.
+    Linked,
+}
+
+/// Progress through the sequence in rule `GB11`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Emoji {
+    None,
+    /// An `Extended_Pictographic`, then `Extend`s.
+    Pictographic,
+    /// An `Extended_Pictographic`, then `Extend`s, then a `ZWJ`.
+    Joined,
+}
+
+/// Forward scanner deciding where grapheme cluster boundaries are.
+///
+/// Every rule in UAX #29 only looks at the text before a position and the
+/// single `char` after it, so the state here is all that is needed.
+#[derive(Clone, Debug)]
+pub(crate) struct Segmenter {
+    prev: Option<Category>,
+    conjunct: Conjunct,
+    emoji: Emoji,
+    /// Whether an odd number of regional indicators precede the position.
+    odd_regional: bool,
+}
+
+impl Segmenter {
+    pub(crate) fn new() -> Self {
+        Segmenter {
+            prev: None,
+            conjunct: Conjunct::None,
+            emoji: Emoji::None,
+            odd_regional: false,
+        }
+    }
+
+    /// Whether there is a boundary before `c`, given the text seen so far.
+    /// `c` is then added to that text.
+    pub(crate) fn is_boundary_before(&mut self, c: char) -> bool {
+        let cat = Category::of(c);
+        let boundary = self.prev.is_none_or(|prev| self.breaks(prev, cat));
+
+        self.conjunct = match cat {
+            Category::InCbConsonant => Conjunct::Consonant,
+            _ if self.conjunct == Conjunct::None => Conjunct::None,
+            _ if is_incb_linker(c) => Conjunct::Linked,
+            _ if is_incb_extend(c) => self.conjunct,
+            _ => Conjunct::None,
+        };
+
+        self.emoji = match (cat, self.emoji) {
+            (Category::ExtendedPictographic, _) | (Category::Extend, Emoji::Pictographic) => {
+                Emoji::Pictographic
+            }
+            (Category::ZWJ, Emoji::Pictographic) => Emoji::Joined,
+            _ => Emoji::None,
+        };
+
+        self.odd_regional = cat == Category::RegionalIndicator && !self.odd_regional;
+        self.prev = Some(cat);
+
+        boundary
+    }
+
+    /// Whether there is a boundary at the start of `rest`, which follows
+    /// the text seen so far. The end of text is always a boundary.
+    pub(crate) fn is_boundary_at(&self, rest: &str) -> bool {
+        rest.chars()
+            .next()
+            .is_none_or(|c| self.clone().is_boundary_before(c))
+    }
+
+    // The rules are applied in order, so arms with the same result can't
+    // always be merged.
+    #[allow(clippy::match_same_arms)]
+    fn breaks(&self, prev: Category, next: Category) -> bool {
+        use Category::{
+            Control, Extend, ExtendedPictographic, InCbConsonant, Prepend, RegionalIndicator,
+            SpacingMark, CR, L, LF, LV, LVT, T, V, ZWJ,
+        };
+
+        match (prev, next) {
+            // GB3
+            (CR, LF) => false,
+            // GB4, GB5
+            (Control | CR | LF, _) | (_, Control | CR | LF) => true,
+            // GB6, GB7, GB8
+            (L, L | V | LV | LVT) | (LV | V, V | T) | (LVT | T, T) => false,
+            // GB9, GB9a, GB9b
+            (_, Extend | ZWJ | SpacingMark) | (Prepend, _) => false,
+            // GB9c
+            (_, InCbConsonant) if self.conjunct == Conjunct::Linked => false,
+            // GB11
+            (ZWJ, ExtendedPictographic) if self.emoji == Emoji::Joined => false,
+            // GB12, GB13
+            (RegionalIndicator, RegionalIndicator) => !self.odd_regional,
+            // GB999
+            _ => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ascii() {
+        assert_eq!("hel", longest_common_prefix_graphemes("hello", "help"));
+        assert_eq!("", longest_common_prefix_graphemes("abc", "xyz"));
+        assert_eq!("abc", longest_common_prefix_graphemes("abc", "abc"));
+        assert_eq!("ab", longest_common_prefix_graphemes("ab", "abc"));
+        assert_eq!("ab", longest_common_prefix_graphemes("abc", "ab"));
+    }
+
+    #[test]
+    fn combining_mark() {
+        assert_eq!(
+            "caf",
+            longest_common_prefix_graphemes("cafe\u{301}", "cafe")
+        );
+        assert_eq!(
+            "caf",
+            longest_common_prefix_graphemes("cafe", "cafe\u{301}")
+        );
+        assert_eq!(
+            "caf",
+            longest_common_prefix_graphemes("cafe\u{301}", "cafe\u{300}")
+        );
+        assert_eq!(
+            "cafe\u{301}",
+            longest_common_prefix_graphemes("cafe\u{301}s", "cafe\u{301}")
+        );
+    }
+
+    #[test]
+    fn zwj_sequence() {
+        // MAN, ZWJ, WOMAN, ZWJ, GIRL and MAN, ZWJ, WOMAN, ZWJ, BOY.
+        let a = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}";
+        let b = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F466}";
+
+        assert_eq!("", longest_common_prefix_graphemes(a, b));
+        assert_eq!("", longest_common_prefix_graphemes(a, "\u{1F468}"));
+        assert_eq!(a, longest_common_prefix_graphemes(a, a));
+    }
+
+    #[test]
+    fn skin_tone_modifier() {
+        assert_eq!(
+            "hi ",
+            longest_common_prefix_graphemes("hi \u{1F44B}\u{1F3FD}", "hi \u{1F44B}\u{1F3FF}")
+        );
+    }
+
+    #[test]
+    fn regional_indicators() {
+        // The flags of France and Fiji share the regional indicator F.
+        let france_fiji = "\u{1F1EB}\u{1F1F7}\u{1F1EB}\u{1F1EF}";
+        let france_finland = "\u{1F1EB}\u{1F1F7}\u{1F1EB}\u{1F1EE}";
+
+        assert_eq!(
+            "\u{1F1EB}\u{1F1F7}",
+            longest_common_prefix_graphemes(france_fiji, france_finland)
+        );
+    }
+
+    #[test]
+    fn hangul_jamo() {
+        // L V and L V T spell the same syllable start.
+        assert_eq!(
+            "",
+            longest_common_prefix_graphemes("\u{1100}\u{1161}", "\u{1100}\u{1161}\u{11A8}")
+        );
+    }
+
+    #[test]
+    fn crlf() {
+        assert_eq!("a", longest_common_prefix_graphemes("a\r\n", "a\r"));
+        assert_eq!("a\r", longest_common_prefix_graphemes("a\rb", "a\rc"));
+    }
+
+    #[test]
+    fn indic_conjunct() {
+        // KA, VIRAMA, SSA is a single cluster; KA, VIRAMA alone is too.
+        assert_eq!(
+            "",
+            longest_common_prefix_graphemes("\u{915}\u{94D}\u{937}", "\u{915}\u{94D}")
+        );
+        // Without the linker, KA and SSA are separate clusters.
+        assert_eq!(
+            "\u{915}",
+            longest_common_prefix_graphemes("\u{915}\u{937}", "\u{915}\u{915}")
+        );
+    }
+
+    #[test]
+    fn prepend() {
+        // U+0600 ARABIC NUMBER SIGN is a prepended concatenation mark.
+        assert_eq!("", longest_common_prefix_graphemes("\u{600}1", "\u{600}2"));
+    }
+
+    #[test]
+    fn empty_iterable() {
+        let iter = [];
+
+        assert_eq!(None, longest_common_prefix_graphemes_in(iter));
+    }
+
+    #[test]
+    fn common_prefix_in_iterable() {
+        let iter = ["cafe\u{301}s", "cafe\u{301}", "cafe\u{301} au lait"];
+
+        assert_eq!(
+            Some("cafe\u{301}"),
+            longest_common_prefix_graphemes_in(iter)
+        );
+
+        let iter = ["cafe\u{301}s", "cafe\u{301}", "cafes"];
+
+        assert_eq!(Some("caf"), longest_common_prefix_graphemes_in(iter));
+    }
+}
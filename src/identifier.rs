@@ -0,0 +1,223 @@
+// Small library to find a common prefix among strings.
+// Copyright (C) 2024  Sohum Mendon
+// SPDX-License-Identifier: MIT
+
+//! Common prefixes made of whole identifier segments.
+//!
+//! Identifiers are split the way `camelCase`, `snake_case` and
+//! `kebab-case` names are read: at lower to upper case transitions, before
+//! the last capital of an acronym, between letters and digits, and around
+//! `_` and `-`.
+
+use crate::longest_common_prefix;
+
+/// Find the longest common prefix between two identifiers that is made of
+/// whole segments of both.
+///
+/// This returns a prefix of `a` without any trailing `_` or `-`, which can
+/// be the empty string `""` if the first segments differ.
+///
+/// ```rust
+/// use lcp::longest_common_prefix_identifier;
+///
+/// let prefix = longest_common_prefix_identifier("parseHttpRequest", "parseHttpResponse");
+/// assert_eq!("parseHttp", prefix);
+///
+/// let prefix = longest_common_prefix_identifier("HTTPServer", "HTTPSocket");
+/// assert_eq!("HTTP", prefix);
+///
+/// let prefix = longest_common_prefix_identifier("max_value", "max_values");
+/// assert_eq!("max", prefix);
+/// ```
+pub fn longest_common_prefix_identifier<'a>(a: &'a str, b: &str) -> &'a str {
+    let len = longest_common_prefix(a, b).len();
+
+    let boundary = a[..len]
+        .char_indices()
+        .map(|(i, _)| i)
+        .chain([len])
+        .rev()
+        .find(|&i| is_boundary(a, i) && is_boundary(b, i))
+        .unwrap_or(0);
+
+    a[..boundary].trim_end_matches(is_separator)
+}
+
+/// Find the longest prefix in an iterable that is made of whole segments
+/// of every identifier.
+///
+/// This returns [`None`] if the passed in iterable is empty. Otherwise,
+/// it returns a [`str`] (including the empty string `""` if there is
+/// no common prefix).
+pub fn longest_common_prefix_identifier_in<'a>(
+    iter: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+    let mut iter = iter.into_iter();
+
+    let mut lcp = iter.next()?;
+
+    for cur in iter {
+        lcp = longest_common_prefix_identifier(lcp, cur);
+
+        if lcp.is_empty() {
+            return Some(lcp);
+        }
+    }
+
+    Some(lcp)
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Class {
+    Separator,
+    Upper,
+    /// Lowercase letters, and letters without case.
+    Lower,
+    Digit,
+    Other,
+}
+
+impl Class {
+    fn of(c: char) -> Self {
+        if is_separator(c) {
+            Class::Separator
+        } else if c.is_uppercase() {
+            Class::Upper
+        } else if c.is_alphabetic() {
+            Class::Lower
+        } else if c.is_numeric() {
+            Class::Digit
+        } else {
+            Class::Other
+        }
+    }
+}
+
+fn is_separator(c: char) -> bool {
+    c == '_' || c == '-'
+}
+
+/// Whether a segment starts or ends at byte `i` of `s`. The start and end
+/// of `s` are always boundaries.
+fn is_boundary(s: &str, i: usize) -> bool {
+    let mut after = s[i..].chars().map(Class::of);
+    let (Some(prev), Some(cur)) = (s[..i].chars().next_back().map(Class::of), after.next()) else {
+        return true;
+    };
+
+    match (prev, cur) {
+        (Class::Upper, Class::Upper) => after.next() == Some(Class::Lower),
+        (Class::Upper, Class::Lower) => false,
+        (Class::Lower, Class::Upper) => true,
+        _ => prev != cur,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn camel_case() {
+        assert_eq!(
+            "parseHttp",
+            longest_common_prefix_identifier("parseHttpRequest", "parseHttpResponse")
+        );
+        assert_eq!(
+            "parse",
+            longest_common_prefix_identifier("parseHttp", "parseHtml")
+        );
+        assert_eq!("", longest_common_prefix_identifier("parse", "parser"));
+        assert_eq!("Foo", longest_common_prefix_identifier("FooBar", "FooBaz"));
+    }
+
+    #[test]
+    fn snake_and_kebab_case() {
+        assert_eq!(
+            "parse_http",
+            longest_common_prefix_identifier("parse_http_request", "parse_http_response")
+        );
+        assert_eq!(
+            "max",
+            longest_common_prefix_identifier("max_value", "max_values")
+        );
+        assert_eq!(
+            "my-crate",
+            longest_common_prefix_identifier("my-crate-core", "my-crate-derive")
+        );
+        assert_eq!(
+            "MAX",
+            longest_common_prefix_identifier("MAX__SIZE", "MAX__LEN")
+        );
+    }
+
+    #[test]
+    fn acronyms() {
+        assert_eq!(
+            "HTTP",
+            longest_common_prefix_identifier("HTTPServer", "HTTPSocket")
+        );
+        // "HTTPS" is a segment of its own, but "HTTPServer" starts with "HTTP".
+        assert_eq!("", longest_common_prefix_identifier("HTTPS", "HTTPServer"));
+        assert_eq!(
+            "getHTTP",
+            longest_common_prefix_identifier("getHTTPServer", "getHTTPClient")
+        );
+        assert_eq!(
+            "MAX",
+            longest_common_prefix_identifier("MAX_VALUE", "MAX_VALID")
+        );
+    }
+
+    #[test]
+    fn digits() {
+        assert_eq!("v", longest_common_prefix_identifier("v1Api", "v2Api"));
+        assert_eq!("v12", longest_common_prefix_identifier("v12Api", "v12Rpc"));
+        assert_eq!("v", longest_common_prefix_identifier("v12", "v123"));
+        assert_eq!("utf", longest_common_prefix_identifier("utf8", "utf16"));
+    }
+
+    #[test]
+    fn whole_identifier() {
+        assert_eq!(
+            "parseHttp",
+            longest_common_prefix_identifier("parseHttp", "parseHttpRequest")
+        );
+        assert_eq!(
+            "parseHttp",
+            longest_common_prefix_identifier("parseHttpRequest", "parseHttp")
+        );
+        assert_eq!("foo", longest_common_prefix_identifier("foo", "foo"));
+        assert_eq!("foo", longest_common_prefix_identifier("foo_", "foo_bar"));
+    }
+
+    #[test]
+    fn non_ascii() {
+        assert_eq!(
+            "stra\u{DF}e",
+            longest_common_prefix_identifier("stra\u{DF}eName", "stra\u{DF}eNummer")
+        );
+        assert_eq!(
+            "\u{C9}cole",
+            longest_common_prefix_identifier("\u{C9}coleNormale", "\u{C9}colePrimaire")
+        );
+    }
+
+    #[test]
+    fn empty_iterable() {
+        let iter = [];
+
+        assert_eq!(None, longest_common_prefix_identifier_in(iter));
+    }
+
+    #[test]
+    fn common_prefix_in_iterable() {
+        let iter = ["parseHttpRequest", "parseHttpResponse", "parseHttpHeaders"];
+
+        assert_eq!(Some("parseHttp"), longest_common_prefix_identifier_in(iter));
+
+        let iter = ["parseHttpRequest", "parseHttpResponse", "parseHtml"];
+
+        assert_eq!(Some("parse"), longest_common_prefix_identifier_in(iter));
+    }
+}
@@ -0,0 +1,263 @@
+// Small library to find a common prefix among strings.
+// Copyright (C) 2024  Sohum Mendon
+// SPDX-License-Identifier: MIT
+
+//! Byte comparison kernels.
+//!
+//! [`mismatch`] finds the first differing byte of two slices. It compares
+//! a `usize` word at a time, and on `x86_64` it uses SSE2 or (when the CPU
+//! supports it) AVX2 for longer inputs.
+
+const WORD: usize = core::mem::size_of::<usize>();
+
+/// Find the index of the first byte that differs between `a` and `b`.
+///
+/// If one slice is a prefix of the other, this returns the length of the
+/// shorter one.
+pub(crate) fn mismatch(a: &[u8], b: &[u8]) -> usize {
+    let len = a.len().min(b.len());
+    let (a, b) = (&a[..len], &b[..len]);
+
+    #[cfg(all(target_arch = "x86_64", not(target_env = "sgx")))]
+    if len >= x86::AVX2_LANES && x86::has_avx2() {
+        // SAFETY: the CPU was checked for AVX2 support.
+        return unsafe { x86::mismatch_avx2(a, b) };
+    }
+
+    #[cfg(all(target_arch = "x86_64", target_feature = "sse2"))]
+    if len >= x86::SSE2_LANES {
+        return x86::mismatch_sse2(a, b);
+    }
+
+    mismatch_words(a, b)
+}
+
+/// Word-at-a-time fallback used on every target.
+///
+/// Both slices must have the same length.
+fn mismatch_words(a: &[u8], b: &[u8]) -> usize {
+    debug_assert_eq!(a.len(), b.len());
+
+    let mut offset = 0;
+
+    for (wa, wb) in a.chunks_exact(WORD).zip(b.chunks_exact(WORD)) {
+        let wa = usize::from_le_bytes(wa.try_into().unwrap());
+        let wb = usize::from_le_bytes(wb.try_into().unwrap());
+
+        let diff = wa ^ wb;
+        if diff != 0 {
+            return offset + diff.trailing_zeros() as usize / 8;
+        }
+
+        offset += WORD;
+    }
+
+    offset + mismatch_bytes(&a[offset..], &b[offset..])
+}
+
+/// Byte-at-a-time comparison for the tails the wider kernels leave behind.
+fn mismatch_bytes(a: &[u8], b: &[u8]) -> usize {
+    a.iter().zip(b).take_while(|(x, y)| x == y).count()
+}
+
+#[cfg(target_arch = "x86_64")]
+mod x86 {
+    pub(super) const SSE2_LANES: usize = 16;
+    pub(super) const AVX2_LANES: usize = 32;
+
+    /// SSE2 kernel. SSE2 is part of the `x86_64` baseline, so no runtime
+    /// check is needed.
+    ///
+    /// Both slices must have the same length.
+    #[cfg(target_feature = "sse2")]
+    pub(super) fn mismatch_sse2(a: &[u8], b: &[u8]) -> usize {
+        use core::arch::x86_64::{__m128i, _mm_cmpeq_epi8, _mm_loadu_si128, _mm_movemask_epi8};
+
+        debug_assert_eq!(a.len(), b.len());
+
+        let mut offset = 0;
+
+        for (ca, cb) in a.chunks_exact(SSE2_LANES).zip(b.chunks_exact(SSE2_LANES)) {
+            // SAFETY: each chunk is exactly 16 bytes long, and the loads are
+            // unaligned.
+            #[allow(clippy::cast_ptr_alignment)]
+            let mask = unsafe {
+                let va = _mm_loadu_si128(ca.as_ptr().cast::<__m128i>());
+                let vb = _mm_loadu_si128(cb.as_ptr().cast::<__m128i>());
+                _mm_movemask_epi8(_mm_cmpeq_epi8(va, vb)).cast_unsigned()
+            };
+
+            if mask != 0xFFFF {
+                return offset + (!mask).trailing_zeros() as usize;
+            }
+
+            offset += SSE2_LANES;
+        }
+
+        offset + super::mismatch_words(&a[offset..], &b[offset..])
+    }
+
+    /// AVX2 kernel.
+    ///
+    /// Both slices must have the same length.
+    ///
+    /// # Safety
+    ///
+    /// The CPU must support AVX2 (see [`has_avx2`]).
+    #[cfg(not(target_env = "sgx"))]
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn mismatch_avx2(a: &[u8], b: &[u8]) -> usize {
+        use core::arch::x86_64::{
+            __m256i, _mm256_cmpeq_epi8, _mm256_loadu_si256, _mm256_movemask_epi8,
+        };
+
+        debug_assert_eq!(a.len(), b.len());
+
+        let mut offset = 0;
+
+        for (ca, cb) in a.chunks_exact(AVX2_LANES).zip(b.chunks_exact(AVX2_LANES)) {
+            // SAFETY: each chunk is exactly 32 bytes long, and the loads are
+            // unaligned.
+            #[allow(clippy::cast_ptr_alignment)]
+            let mask = unsafe {
+                let va = _mm256_loadu_si256(ca.as_ptr().cast::<__m256i>());
+                let vb = _mm256_loadu_si256(cb.as_ptr().cast::<__m256i>());
+                _mm256_movemask_epi8(_mm256_cmpeq_epi8(va, vb)).cast_unsigned()
+            };
+
+            if mask != u32::MAX {
+                return offset + (!mask).trailing_zeros() as usize;
+            }
+
+            offset += AVX2_LANES;
+        }
+
+        offset + super::mismatch_words(&a[offset..], &b[offset..])
+    }
+
+    #[cfg(not(target_env = "sgx"))]
+    pub(super) use detect::has_avx2;
+
+    /// Runtime AVX2 detection that does not depend on `std`.
+    #[cfg(not(target_env = "sgx"))]
+    mod detect {
+        use core::arch::x86_64::{__cpuid, __cpuid_count, _xgetbv};
+        use core::sync::atomic::{AtomicU8, Ordering};
+
+        const UNKNOWN: u8 = 0;
+        const ABSENT: u8 = 1;
+        const PRESENT: u8 = 2;
+
+        static AVX2: AtomicU8 = AtomicU8::new(UNKNOWN);
+
+        /// Whether the running CPU (and OS) support AVX2. The answer is
+        /// cached after the first call.
+        pub(in super::super) fn has_avx2() -> bool {
+            match AVX2.load(Ordering::Relaxed) {
+                UNKNOWN => {
+                    let present = detect();
+                    AVX2.store(if present { PRESENT } else { ABSENT }, Ordering::Relaxed);
+                    present
+                }
+                state => state == PRESENT,
+            }
+        }
+
+        fn detect() -> bool {
+            const OSXSAVE: u32 = 1 << 27;
+            const AVX: u32 = 1 << 28;
+            const AVX2: u32 = 1 << 5;
+            // XMM and YMM state must both be enabled by the OS.
+            const XCR0_AVX_STATE: u64 = 0b110;
+
+            // SAFETY: `cpuid` is always available on x86_64. The intrinsics
+            // are only safe functions since Rust 1.94, after our MSRV.
+            #[allow(unused_unsafe)]
+            let (max_leaf, leaf1) = unsafe { (__cpuid(0).eax, __cpuid(1)) };
+            if max_leaf < 7 || leaf1.ecx & (OSXSAVE | AVX) != OSXSAVE | AVX {
+                return false;
+            }
+
+            // SAFETY: OSXSAVE is set, so `xgetbv` is enabled.
+            if unsafe { xcr0() } & XCR0_AVX_STATE != XCR0_AVX_STATE {
+                return false;
+            }
+
+            // SAFETY: leaf 7 is supported, see `max_leaf` above.
+            #[allow(unused_unsafe)]
+            let leaf7 = unsafe { __cpuid_count(7, 0) };
+
+            leaf7.ebx & AVX2 != 0
+        }
+
+        #[target_feature(enable = "xsave")]
+        unsafe fn xcr0() -> u64 {
+            _xgetbv(0)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAX_LEN: usize = 100;
+
+    /// Run `kernel` on equal inputs of every length up to [`MAX_LEN`], and on
+    /// inputs that differ at every possible position.
+    fn check(kernel: impl Fn(&[u8], &[u8]) -> usize) {
+        let mut a = [0u8; MAX_LEN];
+        for (i, byte) in a.iter_mut().enumerate() {
+            *byte = u8::try_from(i % 251).unwrap();
+        }
+
+        for len in 0..=MAX_LEN {
+            let mut b = a;
+            assert_eq!(len, kernel(&a[..len], &b[..len]));
+
+            for diff in 0..len {
+                b[diff] ^= 0x80;
+                assert_eq!(diff, kernel(&a[..len], &b[..len]));
+                assert_eq!(mismatch_bytes(&a[..len], &b[..len]), diff);
+                b[diff] ^= 0x80;
+            }
+        }
+    }
+
+    #[test]
+    fn bytes() {
+        check(mismatch_bytes);
+    }
+
+    #[test]
+    fn words() {
+        check(mismatch_words);
+    }
+
+    #[test]
+    fn dispatch() {
+        check(mismatch);
+    }
+
+    #[test]
+    fn different_lengths() {
+        assert_eq!(3, mismatch(b"abc", b"abcdef"));
+        assert_eq!(3, mismatch(b"abcdef", b"abc"));
+        assert_eq!(0, mismatch(b"", b"abc"));
+    }
+
+    #[cfg(all(target_arch = "x86_64", target_feature = "sse2"))]
+    #[test]
+    fn sse2() {
+        check(x86::mismatch_sse2);
+    }
+
+    #[cfg(all(target_arch = "x86_64", not(target_env = "sgx")))]
+    #[test]
+    fn avx2() {
+        if x86::has_avx2() {
+            // SAFETY: the CPU was checked for AVX2 support.
+            check(|a, b| unsafe { x86::mismatch_avx2(a, b) });
+        }
+    }
+}
@@ -4,8 +4,98 @@
 
 //! Find the longest common prefix in a string.
 //!
-//! There are only two publicly exported methods:
-//! [`longest_common_prefix`] and [`longest_common_prefix_in`].
+//! The main entry points are [`longest_common_prefix`] and
+//! [`longest_common_prefix_in`]. The same operations are available for
+//! arbitrary slices through [`longest_common_prefix_slice`] and
+//! [`longest_common_prefix_slice_in`], or [`longest_common_prefix_bytes`]
+//! and [`longest_common_prefix_bytes_in`] for byte slices. Common suffixes
+//! are found by [`longest_common_suffix`] and [`longest_common_suffix_in`].
+//! [`longest_common_prefix_utf16`] and [`longest_common_prefix_utf16_in`]
+//! work on UTF-16 buffers without splitting surrogate pairs.
+//! [`longest_common_prefix_detailed`] and
+//! [`longest_common_prefix_detailed_in`] return an [`LcpResult`], which
+//! also says how long the prefix is, which input shortened it, and the
+//! `char` where each input differs.
+//!
+//! [`longest_common_prefix_by`] and [`longest_common_prefix_in_by`] compare
+//! `char`s with a custom predicate, and [`longest_common_prefix_by_key`] and
+//! [`longest_common_prefix_in_by_key`] compare a key of each `char`.
+//!
+//! [`longest_common_prefix_graphemes`] and
+//! [`longest_common_prefix_graphemes_in`] only return prefixes that end on
+//! an extended grapheme cluster boundary, and
+//! [`longest_common_prefix_by_delimiter`] and
+//! [`longest_common_prefix_by_delimiter_in`] only return whole tokens
+//! between [`Delimiter`]s. [`longest_common_prefix_identifier`] and
+//! [`longest_common_prefix_identifier_in`] do the same for the segments of
+//! `camelCase`, `snake_case` and `kebab-case` identifiers.
+//!
+//! [`longest_common_prefix_ignore_case`] and
+//! [`longest_common_prefix_ignore_case_in`] compare strings after Unicode
+//! case folding, and [`longest_common_prefix_canonical`] and
+//! [`longest_common_prefix_canonical_in`] treat canonically equivalent
+//! sequences (such as NFC and NFD forms) as equal.
+//! [`longest_common_prefix_ignore_whitespace`] and
+//! [`longest_common_prefix_ignore_whitespace_in`] treat every run of
+//! whitespace as equal, and their `_trim_start` forms also ignore
+//! whitespace at the start. These are all built on the
+//! [`normalize`] module, where [`longest_common_prefix_with`] and
+//! [`longest_common_prefix_in_with`] compare strings after any pipeline of
+//! [`Normalizer`](normalize::Normalizer)s.
+//!
+//! [`longest_common_path`] and [`longest_common_path_in`] only cut paths
+//! at separators, and [`longest_common_windows_path`] and
+//! [`longest_common_windows_path_in`] do the same for Windows paths on any
+//! host. [`longest_common_path_bytes`] and [`longest_common_path_bytes_in`]
+//! compare `/`-separated paths that may not be UTF-8.
+#![cfg_attr(
+    feature = "std",
+    doc = "With the `std` feature, [`longest_common_std_path`] and \
+           [`longest_common_std_path_in`] do the same for [`std::path::Path`]."
+)]
+//!
+//! [`longest_common_prefix_in_as_ref`] takes references to anything that
+//! is [`AsRef<str>`], and borrows its result from the first one. The other
+//! `_in` functions take `&str`s, so map such items with
+//! `.map(AsRef::as_ref)`, or use [`LcpExt`].
+#![cfg_attr(
+    feature = "alloc",
+    doc = "With the `alloc` feature, [`longest_common_prefix_in_owned`] and \
+           [`longest_common_prefix_in_cow`] take owned items instead."
+)]
+//!
+//! The [`CommonPrefix`] trait has one `common_prefix` method for strings,
+//! slices, `char` iterators, paths, integers and IP addresses, and
+//! [`common_prefix_in`] finds the common prefix of any iterable of them.
+//! The [`LcpExt`] extension trait adds the same operations as methods on
+//! iterators, such as `names.iter().longest_common_prefix()`.
+//!
+//! [`BorrowedLcpAccumulator`] keeps the running common prefix of strings
+//! that arrive one at a time, and reports when it gets shorter.
+#![cfg_attr(
+    feature = "alloc",
+    doc = "With the `alloc` feature, [`LcpAccumulator`] does the same without \
+           borrowing the strings."
+)]
+//!
+//! [`common_prefix_len_bytes`] and [`common_prefix_len_bytes_in`] are
+//! `const fn`s, so common prefixes of string constants can be found at
+//! compile time.
+//!
+#![cfg_attr(
+    feature = "std",
+    doc = "With the `std` feature, [`longest_common_prefix_os`] and \
+           [`longest_common_prefix_path`] (and their `_in` forms) find the \
+           common prefix of strings that may not be UTF-8."
+)]
+//!
+#![cfg_attr(
+    feature = "alloc",
+    doc = "With the `alloc` feature, [`longest_common_substring`] and \
+           [`longest_common_substring_in`] find the longest string that occurs \
+           anywhere in every input, and the [`suffix`] module builds suffix \
+           arrays and LCP arrays."
+)]
 //!
 //! Example
 //! ```rust
@@ -23,34 +113,137 @@
 //!
 //! let intoiter = ["there's no", "common prefix", "here"];
 //! assert_eq!(Some(""), longest_common_prefix_in(intoiter));
+//!
+//! let prefix = lcp::longest_common_prefix_slice(&[1, 2, 3], &[1, 2, 4]);
+//! assert_eq!(&[1, 2], prefix);
 //! ```
 
 #![no_std]
 #![deny(clippy::all, clippy::pedantic)]
 #![allow(clippy::must_use_candidate)]
 
+mod accumulator;
+mod by;
+mod canonical;
+mod casefold;
+mod common;
+mod constant;
+mod delimiter;
+mod detailed;
+mod ext;
+mod grapheme;
+mod identifier;
+mod kernel;
+pub mod normalize;
+#[cfg(feature = "std")]
+mod os;
+#[cfg(feature = "alloc")]
+mod owned;
+mod path;
+mod slice;
+#[cfg(feature = "alloc")]
+mod substring;
+#[cfg(feature = "alloc")]
+pub mod suffix;
+mod utf16;
+mod whitespace;
+
+pub use accumulator::BorrowedLcpAccumulator;
+#[cfg(feature = "alloc")]
+pub use accumulator::LcpAccumulator;
+pub use by::{
+    longest_common_prefix_by, longest_common_prefix_by_key, longest_common_prefix_in_by,
+    longest_common_prefix_in_by_key,
+};
+pub use canonical::{longest_common_prefix_canonical, longest_common_prefix_canonical_in};
+pub use casefold::{longest_common_prefix_ignore_case, longest_common_prefix_ignore_case_in};
+pub use common::{common_prefix_in, CommonPrefix};
+pub use constant::{common_prefix_len_bytes, common_prefix_len_bytes_in};
+pub use delimiter::{
+    longest_common_prefix_by_delimiter, longest_common_prefix_by_delimiter_in,
+    longest_common_prefix_by_delimiter_inclusive, longest_common_prefix_by_delimiter_inclusive_in,
+    Delimiter,
+};
+pub use detailed::{
+    longest_common_prefix_detailed, longest_common_prefix_detailed_in, DivergentChars, LcpResult,
+};
+pub use ext::LcpExt;
+pub use grapheme::{longest_common_prefix_graphemes, longest_common_prefix_graphemes_in};
+pub use identifier::{longest_common_prefix_identifier, longest_common_prefix_identifier_in};
+pub use normalize::{longest_common_prefix_in_with, longest_common_prefix_with};
+#[cfg(feature = "std")]
+pub use os::{
+    longest_common_prefix_os, longest_common_prefix_os_in, longest_common_prefix_path,
+    longest_common_prefix_path_in,
+};
+#[cfg(feature = "alloc")]
+pub use owned::{longest_common_prefix_in_cow, longest_common_prefix_in_owned};
+pub use path::{
+    longest_common_path, longest_common_path_bytes, longest_common_path_bytes_in,
+    longest_common_path_in, longest_common_windows_path, longest_common_windows_path_in,
+};
+#[cfg(feature = "std")]
+pub use path::{longest_common_std_path, longest_common_std_path_in};
+pub use slice::{
+    longest_common_prefix_bytes, longest_common_prefix_bytes_in, longest_common_prefix_slice,
+    longest_common_prefix_slice_in,
+};
+#[cfg(feature = "alloc")]
+pub use substring::{longest_common_substring, longest_common_substring_in};
+pub use utf16::{
+    longest_common_prefix_utf16, longest_common_prefix_utf16_in, utf16_to_utf8_offset,
+    utf8_to_utf16_offset,
+};
+pub use whitespace::{
+    longest_common_prefix_ignore_whitespace, longest_common_prefix_ignore_whitespace_in,
+    longest_common_prefix_ignore_whitespace_trim_start,
+    longest_common_prefix_ignore_whitespace_trim_start_in,
+};
+
 use core::ptr;
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
 /// Find the longest common prefix between two strings.
 ///
 /// This returns a [`str`], which can be the empty string `""` if
-/// there is no common prefix.
-pub fn longest_common_prefix<'a>(a: &'a str, b: &'a str) -> &'a str {
+/// there is no common prefix. The result only borrows from `a`, so `b`
+/// can be a temporary.
+///
+/// The strings are compared as bytes, and the result is moved back to
+/// the previous `char` boundary, so it never splits a multi-byte `char`.
+pub fn longest_common_prefix<'a>(a: &'a str, b: &str) -> &'a str {
+    &a[..common_prefix_len(a, b)]
+}
+
+/// Find the length in bytes of the longest common prefix between two
+/// strings.
+///
+/// This is always a `char` boundary in both strings.
+///
+/// ```rust
+/// use lcp::common_prefix_len;
+///
+/// assert_eq!(3, common_prefix_len("hello", "help"));
+/// // 'é' and 'è' share their first byte.
+/// assert_eq!(1, common_prefix_len("h\u{E9}", "h\u{E8}"));
+/// ```
+pub fn common_prefix_len(a: &str, b: &str) -> usize {
     if ptr::eq(a, b) {
-        return a;
+        return a.len();
     }
 
-    for (i, (ac, bc)) in a.chars().zip(b.chars()).enumerate() {
-        if ac != bc {
-            return &a[..i];
-        }
-    }
+    let mut len = kernel::mismatch(a.as_bytes(), b.as_bytes());
 
-    if a.len() < b.len() {
-        a
-    } else {
-        b
+    // Both strings agree up to `len`, so a boundary in `a` is also one in `b`.
+    while !a.is_char_boundary(len) {
+        len -= 1;
     }
+
+    len
 }
 
 /// Find the longest prefix in an iterable.
@@ -76,6 +269,71 @@ pub fn longest_common_prefix_in<'a>(iter: impl IntoIterator<Item = &'a str>) ->
     Some(lcp)
 }
 
+/// Find the longest prefix in an iterable of references to anything that
+/// is [`AsRef<str>`], such as [`str`], `String` or `Cow<str>`.
+///
+/// This works like [`longest_common_prefix_in`], and borrows its result
+/// from the first item.
+///
+/// ```rust
+/// use lcp::longest_common_prefix_in_as_ref;
+///
+/// let names = [String::from("report-2024.txt"), String::from("report-2023.txt")];
+///
+/// assert_eq!(Some("report-202"), longest_common_prefix_in_as_ref(&names));
+/// ```
+pub fn longest_common_prefix_in_as_ref<'a, S>(
+    iter: impl IntoIterator<Item = &'a S>,
+) -> Option<&'a str>
+where
+    S: AsRef<str> + ?Sized + 'a,
+{
+    longest_common_prefix_in(iter.into_iter().map(AsRef::as_ref))
+}
+
+/// Find the longest common suffix between two strings.
+///
+/// This returns a [`str`], which can be the empty string `""` if
+/// there is no common suffix.
+pub fn longest_common_suffix<'a>(a: &'a str, b: &str) -> &'a str {
+    if ptr::eq(a, b) {
+        return a;
+    }
+
+    let mut start = a.len();
+
+    for ((i, ac), bc) in a.char_indices().rev().zip(b.chars().rev()) {
+        if ac != bc {
+            break;
+        }
+
+        start = i;
+    }
+
+    &a[start..]
+}
+
+/// Find the longest suffix in an iterable.
+///
+/// This returns [`None`] if the passed in iterable is empty. Otherwise,
+/// it returns a [`str`] (including the empty string `""` if there is
+/// no common suffix).
+pub fn longest_common_suffix_in<'a>(iter: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    let mut iter = iter.into_iter();
+
+    let mut lcs = iter.next()?;
+
+    for cur in iter {
+        lcs = longest_common_suffix(lcs, cur);
+
+        if lcs.is_empty() {
+            return Some(lcs);
+        }
+    }
+
+    Some(lcs)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -83,6 +341,39 @@ mod tests {
     const EMPTY: &str = "";
     const HELLO: &str = "hello";
 
+    /// The original `char`-at-a-time implementation, kept as a reference
+    /// for the byte kernels.
+    fn scalar_lcp<'a>(a: &'a str, b: &str) -> &'a str {
+        for ((i, ac), bc) in a.char_indices().zip(b.chars()) {
+            if ac != bc {
+                return &a[..i];
+            }
+        }
+
+        if a.len() < b.len() {
+            a
+        } else {
+            &a[..b.len()]
+        }
+    }
+
+    /// A small xorshift generator, so the differential tests need no
+    /// dependencies.
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn below(&mut self, n: usize) -> usize {
+            usize::try_from(self.next() % n as u64).unwrap()
+        }
+    }
+
     #[test]
     fn one_is_empty() {
         assert_eq!(EMPTY, longest_common_prefix(HELLO, EMPTY));
@@ -120,6 +411,107 @@ mod tests {
         assert_eq!(common, longest_common_prefix(help, HELLO));
     }
 
+    #[test]
+    fn multi_byte_common_prefix() {
+        assert_eq!("hél", longest_common_prefix("héllo", "hélp"));
+        assert_eq!("h", longest_common_prefix("héllo", "hello"));
+        assert_eq!("日本", longest_common_prefix("日本語", "日本人"));
+        assert_eq!("👋", longest_common_prefix("👋🏽", "👋🏿"));
+    }
+
+    #[test]
+    fn diverge_inside_multi_byte_char() {
+        // 'é' (C3 A9) and 'ê' (C3 AA) share their first byte.
+        assert_eq!("h", longest_common_prefix("hé", "hê"));
+        // '€' (E2 82 AC) and '₭' (E2 82 AD) share their first two bytes.
+        assert_eq!("1", longest_common_prefix("1€", "1₭"));
+        // '😀' (F0 9F 98 80) and '😁' (F0 9F 98 81) share their first three bytes.
+        assert_eq!(":", longest_common_prefix(":😀", ":😁"));
+    }
+
+    /// Checks every char of the given UTF-8 length against its successor,
+    /// which shares all but (at least) the final byte.
+    fn check_all_chars_of_len(len: usize) {
+        let mut buf_a = [0u8; 8];
+        let mut buf_b = [0u8; 8];
+
+        for c in (0..=u32::from(char::MAX)).filter_map(char::from_u32) {
+            if c.len_utf8() != len {
+                continue;
+            }
+
+            buf_a[0] = b'>';
+            let end = 1 + c.encode_utf8(&mut buf_a[1..]).len();
+            buf_a[end] = b'x';
+            let a = core::str::from_utf8(&buf_a[..=end]).unwrap();
+
+            buf_b[..end].copy_from_slice(&buf_a[..end]);
+            buf_b[end] = b'y';
+            let b = core::str::from_utf8(&buf_b[..=end]).unwrap();
+
+            assert_eq!(&a[..end], longest_common_prefix(a, b));
+
+            if let Some(d) = char::from_u32(u32::from(c) + 1).filter(|d| d.len_utf8() == len) {
+                buf_b[0] = b'>';
+                let end = 1 + d.encode_utf8(&mut buf_b[1..]).len();
+                let b = core::str::from_utf8(&buf_b[..end]).unwrap();
+
+                assert_eq!(">", longest_common_prefix(a, b));
+                assert_eq!(">", longest_common_prefix(b, a));
+            }
+        }
+    }
+
+    #[test]
+    fn all_two_byte_chars() {
+        check_all_chars_of_len(2);
+    }
+
+    #[test]
+    fn all_three_byte_chars() {
+        check_all_chars_of_len(3);
+    }
+
+    #[test]
+    fn all_four_byte_chars() {
+        check_all_chars_of_len(4);
+    }
+
+    #[test]
+    fn matches_scalar_loop() {
+        // Mixed 1- to 4-byte chars, with few enough of them that long
+        // common prefixes are likely.
+        const ALPHABET: [char; 6] = ['a', 'b', 'é', 'ê', '€', '😀'];
+
+        let mut rng = Rng(0x2545_F491_4F6C_DD1D);
+        let mut buf_a = [0u8; 512];
+        let mut buf_b = [0u8; 512];
+
+        for _ in 0..2000 {
+            let mut len_a = 0;
+            for _ in 0..rng.below(120) {
+                let c = ALPHABET[rng.below(ALPHABET.len())];
+                len_a += c.encode_utf8(&mut buf_a[len_a..]).len();
+            }
+
+            let a = core::str::from_utf8(&buf_a[..len_a]).unwrap();
+
+            // Share a random number of chars with `a`, then diverge.
+            let shared = a.char_indices().nth(rng.below(a.chars().count() + 1));
+            let mut len_b = shared.map_or(len_a, |(i, _)| i);
+            buf_b[..len_b].copy_from_slice(&buf_a[..len_b]);
+            for _ in 0..rng.below(120) {
+                let c = ALPHABET[rng.below(ALPHABET.len())];
+                len_b += c.encode_utf8(&mut buf_b[len_b..]).len();
+            }
+
+            let b = core::str::from_utf8(&buf_b[..len_b]).unwrap();
+
+            assert_eq!(scalar_lcp(a, b), longest_common_prefix(a, b));
+            assert_eq!(scalar_lcp(b, a), longest_common_prefix(b, a));
+        }
+    }
+
     #[test]
     fn empty_iterable() {
         let iter = [];
@@ -127,6 +519,52 @@ mod tests {
         assert_eq!(None, longest_common_prefix_in(iter));
     }
 
+    #[test]
+    fn result_borrows_from_first() {
+        let a = "hello world";
+
+        let prefix = {
+            let buf = *b"hello moon";
+            longest_common_prefix(a, core::str::from_utf8(&buf).unwrap())
+        };
+
+        assert_eq!("hello ", prefix);
+    }
+
+    #[test]
+    fn common_prefix_len_is_char_boundary() {
+        assert_eq!(0, common_prefix_len("", "abc"));
+        assert_eq!(3, common_prefix_len("abc", "abc"));
+        assert_eq!(1, common_prefix_len("h\u{E9}", "h\u{E8}"));
+        assert_eq!(3, common_prefix_len("h\u{E9}", "h\u{E9}x"));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn common_prefix_in_strings() {
+        use alloc::borrow::Cow;
+        use alloc::string::String;
+
+        let strings = [String::from("prefix-a"), String::from("prefix-b")];
+
+        assert_eq!(Some("prefix-"), longest_common_prefix_in_as_ref(&strings));
+        assert_eq!(
+            Some(""),
+            longest_common_suffix_in(strings.iter().map(String::as_str))
+        );
+
+        let cows = [Cow::Borrowed("prefix-a"), Cow::Owned(String::from("pre"))];
+
+        assert_eq!(Some("pre"), longest_common_prefix_in_as_ref(&cows));
+
+        let names = [String::from("a.rs"), String::from("b.rs")];
+
+        assert_eq!(
+            Some(".rs"),
+            longest_common_suffix_in(names.iter().map(AsRef::as_ref))
+        );
+    }
+
     #[test]
     fn one_element_in_iterable() {
         let iter = [EMPTY];
@@ -147,4 +585,71 @@ mod tests {
 
         assert_eq!(Some("hel"), longest_common_prefix_in(iter));
     }
+
+    #[test]
+    fn multi_byte_common_prefix_in_iterable() {
+        let iter = ["naïve", "naïveté", "naïf"];
+
+        assert_eq!(Some("naï"), longest_common_prefix_in(iter));
+    }
+
+    #[test]
+    fn suffix_one_is_empty() {
+        assert_eq!(EMPTY, longest_common_suffix(HELLO, EMPTY));
+        assert_eq!(EMPTY, longest_common_suffix(EMPTY, HELLO));
+    }
+
+    #[test]
+    fn suffix_both_are_same_ptr() {
+        assert_eq!(EMPTY, longest_common_suffix(EMPTY, EMPTY));
+        assert_eq!(HELLO, longest_common_suffix(HELLO, HELLO));
+    }
+
+    #[test]
+    fn no_common_suffix() {
+        assert_eq!(EMPTY, longest_common_suffix(HELLO, "help"));
+        assert_eq!(EMPTY, longest_common_suffix("help", HELLO));
+    }
+
+    #[test]
+    fn common_suffix() {
+        assert_eq!(".tar.gz", longest_common_suffix("a.tar.gz", "bb.tar.gz"));
+        assert_eq!("ello", longest_common_suffix(HELLO, "jello"));
+        assert_eq!("llo", longest_common_suffix("llo", HELLO));
+        assert_eq!(HELLO, longest_common_suffix(HELLO, "othello"));
+    }
+
+    #[test]
+    fn multi_byte_common_suffix() {
+        assert_eq!("té", longest_common_suffix("naïveté", "été"));
+        // 'é' (C3 A9) and 'ɩ' (C9 A9) share their last byte.
+        assert_eq!("x", longest_common_suffix("éx", "ɩx"));
+        // '😀' (F0 9F 98 80) and '瘀' (E7 98 80) share their last two bytes.
+        assert_eq!("", longest_common_suffix("😀", "瘀"));
+    }
+
+    #[test]
+    fn empty_iterable_suffix() {
+        let iter = [];
+
+        assert_eq!(None, longest_common_suffix_in(iter));
+    }
+
+    #[test]
+    fn common_suffix_in_iterable() {
+        let iter = ["example.com", "mail.example.com", "www.example.com"];
+
+        assert_eq!(Some("example.com"), longest_common_suffix_in(iter));
+
+        let iter = ["lib-1.2.3", "app-2.2.3", "tool-0.3"];
+
+        assert_eq!(Some(".3"), longest_common_suffix_in(iter));
+    }
+
+    #[test]
+    fn no_common_suffix_in_iterable() {
+        let iter = [HELLO, EMPTY, "jello"];
+
+        assert_eq!(Some(""), longest_common_suffix_in(iter));
+    }
 }
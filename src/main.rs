@@ -4,20 +4,62 @@
 
 #![deny(clippy::all, clippy::pedantic)]
 
-use lcp::longest_common_prefix;
+use std::io::{self, Write};
 
-fn main() {
-    let lcp = std::env::args()
-        .skip(1)
-        .reduce(|acc, s| longest_common_prefix(&acc, &s).to_owned());
+use lcp::{longest_common_path_bytes, longest_common_prefix_bytes};
 
-    if let Some(lcp) = lcp {
-        if lcp.is_empty() {
-            println!("<empty>");
+fn main() -> io::Result<()> {
+    let mut args = std::env::args_os().skip(1).peekable();
+
+    let mut path = false;
+    let mut lossy = false;
+    while let Some(flag) = args.next_if(|arg| arg == "--path" || arg == "--lossy") {
+        if flag == "--path" {
+            path = true;
         } else {
-            println!("{lcp}");
+            lossy = true;
         }
+    }
+
+    // The arguments are compared as bytes, so they don't have to be UTF-8.
+    let common: for<'a> fn(&'a [u8], &[u8]) -> &'a [u8] = if path {
+        longest_common_path_bytes
+    } else {
+        common_prefix
+    };
+
+    let Some(first) = args.next() else {
+        eprintln!("Usage: lcp [--path] [--lossy] [word...]");
+        return Ok(());
+    };
+
+    let lcp = args.fold(first.as_encoded_bytes(), |lcp, arg| {
+        common(lcp, arg.as_encoded_bytes())
+    });
+
+    if lcp.is_empty() {
+        println!("<empty>");
+    } else if lossy {
+        println!("{}", String::from_utf8_lossy(lcp));
     } else {
-        eprintln!("Usage: lcp [word...]");
+        let mut stdout = io::stdout().lock();
+        stdout.write_all(lcp)?;
+        stdout.write_all(b"\n")?;
     }
+
+    Ok(())
+}
+
+/// The common prefix of two arguments, as in `longest_common_prefix_os`.
+/// Outside Unix, it is moved back to the end of a UTF-8 `char`.
+fn common_prefix<'a>(a: &'a [u8], b: &[u8]) -> &'a [u8] {
+    let mut len = longest_common_prefix_bytes(a, b).len();
+
+    if cfg!(not(unix)) {
+        while a.get(len).is_some_and(|byte| byte & 0xC0 == 0x80) {
+            len -= 1;
+        }
+    }
+
+    &a[..len]
 }
@@ -0,0 +1,419 @@
+// Small library to find a common prefix among strings.
+// Copyright (C) 2024  Sohum Mendon
+// SPDX-License-Identifier: MIT
+
+//! Composable comparison pipelines.
+//!
+//! A [`Normalizer`] maps a stream of [`Unit`]s to another stream of
+//! [`Unit`]s, and normalizers are chained with [`Normalizer::then`]. Every
+//! unit remembers the bytes of the original string it came from, so
+//! [`longest_common_prefix_with`] can return a slice of the original
+//! string.
+//!
+//! ```rust
+//! use lcp::normalize::{CaseFold, CollapseWhitespace, Nfc, Normalizer};
+//! use lcp::longest_common_prefix_with;
+//!
+//! let normalizer = CaseFold.then(Nfc).then(CollapseWhitespace);
+//!
+//! let prefix = longest_common_prefix_with(&normalizer, "Caf\u{E9}  Au Lait", "cafe\u{301} au\tlai");
+//! assert_eq!("Caf\u{E9}  Au Lai", prefix);
+//! ```
+
+use core::iter::{Peekable, SkipWhile};
+use core::ptr;
+
+pub use crate::canonical::{Decomposed, Nfc};
+pub use crate::casefold::{CaseFold, Folded, SimpleCaseFold};
+
+/// A `char` to compare, and the span of the original string it came from.
+///
+/// When one source `char` becomes several units, or several source `char`s
+/// become one, the units share the span of all of those `char`s. A prefix
+/// of the original string can only end where a span ends.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Unit {
+    /// The `char` to compare.
+    pub ch: char,
+    /// The byte offset in the original string where the span starts.
+    pub start: usize,
+    /// The byte offset in the original string just after the span.
+    pub end: usize,
+}
+
+/// A step in a comparison pipeline.
+///
+/// Implementations should give every unit that comes from the same input
+/// units the same span, covering all of those input units.
+pub trait Normalizer {
+    /// The normalized units of `I`.
+    type Output<I: Iterator<Item = Unit>>: Iterator<Item = Unit>;
+
+    /// Normalize a stream of units.
+    fn normalize<I: Iterator<Item = Unit>>(&self, units: I) -> Self::Output<I>;
+
+    /// Apply `next` to the output of this normalizer.
+    fn then<N: Normalizer>(self, next: N) -> Then<Self, N>
+    where
+        Self: Sized,
+    {
+        Then { first: self, next }
+    }
+}
+
+impl<N: Normalizer + ?Sized> Normalizer for &N {
+    type Output<I: Iterator<Item = Unit>> = N::Output<I>;
+
+    fn normalize<I: Iterator<Item = Unit>>(&self, units: I) -> N::Output<I> {
+        (**self).normalize(units)
+    }
+}
+
+/// Two normalizers applied one after the other, made by
+/// [`Normalizer::then`].
+#[derive(Clone, Copy, Default, Debug)]
+pub struct Then<A, B> {
+    first: A,
+    next: B,
+}
+
+impl<A: Normalizer, B: Normalizer> Normalizer for Then<A, B> {
+    type Output<I: Iterator<Item = Unit>> = B::Output<A::Output<I>>;
+
+    fn normalize<I: Iterator<Item = Unit>>(&self, units: I) -> Self::Output<I> {
+        self.next.normalize(self.first.normalize(units))
+    }
+}
+
+/// Find the longest common prefix between two strings, comparing them
+/// after `normalizer`.
+///
+/// This returns a prefix of `a`, which can be the empty string `""` if
+/// there is no common prefix. The prefix only ends where both strings
+/// finish a span of units.
+///
+/// ```rust
+/// use lcp::longest_common_prefix_with;
+/// use lcp::normalize::{CaseFold, Normalizer, StripAnsi};
+///
+/// let prefix = longest_common_prefix_with(&StripAnsi.then(CaseFold), "\x1b[1mERROR\x1b[0m: disk", "error: DISK full");
+/// assert_eq!("\x1b[1mERROR\x1b[0m: disk", prefix);
+/// ```
+pub fn longest_common_prefix_with<'a>(
+    normalizer: &impl Normalizer,
+    a: &'a str,
+    b: &str,
+) -> &'a str {
+    if ptr::eq(a, b) {
+        return a;
+    }
+
+    let a_units = normalizer.normalize(units(a));
+    let b_units = normalizer.normalize(units(b));
+
+    &a[..aligned_prefix_len(a_units, b_units)]
+}
+
+/// Find the longest prefix in an iterable, comparing strings after
+/// `normalizer`.
+///
+/// This returns [`None`] if the passed in iterable is empty. Otherwise,
+/// it returns a prefix of the first string (including the empty string
+/// `""` if there is no common prefix).
+pub fn longest_common_prefix_in_with<'a>(
+    normalizer: &impl Normalizer,
+    iter: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+    let mut iter = iter.into_iter();
+
+    let mut lcp = iter.next()?;
+
+    for cur in iter {
+        lcp = longest_common_prefix_with(normalizer, lcp, cur);
+
+        if lcp.is_empty() {
+            return Some(lcp);
+        }
+    }
+
+    Some(lcp)
+}
+
+/// The units of a string, one for each `char`.
+pub(crate) fn units(s: &str) -> impl Iterator<Item = Unit> + '_ {
+    s.char_indices().map(|(i, ch)| Unit {
+        ch,
+        start: i,
+        end: i + ch.len_utf8(),
+    })
+}
+
+/// Find the byte length of the longest prefix of `a`'s source whose units
+/// equal a prefix of `b`'s units, and which ends where both streams finish
+/// a span.
+pub(crate) fn aligned_prefix_len(
+    a: impl IntoIterator<Item = Unit>,
+    b: impl IntoIterator<Item = Unit>,
+) -> usize {
+    let mut a = a.into_iter().peekable();
+    let mut b = b.into_iter().peekable();
+    let mut len = 0;
+
+    while let (Some(x), Some(y)) = (a.next(), b.next()) {
+        if x.ch != y.ch {
+            break;
+        }
+
+        if ends_span(x, a.peek()) && ends_span(y, b.peek()) {
+            len = x.end;
+        }
+    }
+
+    len
+}
+
+/// Whether `unit` is the last unit of its span.
+fn ends_span(unit: Unit, next: Option<&Unit>) -> bool {
+    next.is_none_or(|next| next.start >= unit.end)
+}
+
+/// Every run of whitespace compares equal to a single `' '`.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct CollapseWhitespace;
+
+impl Normalizer for CollapseWhitespace {
+    type Output<I: Iterator<Item = Unit>> = Collapsed<I>;
+
+    fn normalize<I: Iterator<Item = Unit>>(&self, units: I) -> Collapsed<I> {
+        Collapsed {
+            source: units.peekable(),
+        }
+    }
+}
+
+/// The units of [`CollapseWhitespace`].
+#[derive(Clone, Debug)]
+pub struct Collapsed<I: Iterator<Item = Unit>> {
+    source: Peekable<I>,
+}
+
+impl<I: Iterator<Item = Unit>> Iterator for Collapsed<I> {
+    type Item = Unit;
+
+    fn next(&mut self) -> Option<Unit> {
+        let mut unit = self.source.next()?;
+
+        if unit.ch.is_whitespace() {
+            unit.ch = ' ';
+            while let Some(next) = self.source.next_if(|next| next.ch.is_whitespace()) {
+                unit.end = next.end;
+            }
+        }
+
+        Some(unit)
+    }
+}
+
+/// Whitespace at the start is skipped.
+///
+/// The skipped whitespace is still part of the original string, so it is
+/// included in any non-empty prefix of the first string.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct TrimStart;
+
+impl Normalizer for TrimStart {
+    type Output<I: Iterator<Item = Unit>> = SkipWhile<I, fn(&Unit) -> bool>;
+
+    fn normalize<I: Iterator<Item = Unit>>(&self, units: I) -> Self::Output<I> {
+        units.skip_while(|unit| unit.ch.is_whitespace())
+    }
+}
+
+/// ANSI escape sequences, such as colors, are skipped.
+///
+/// This removes CSI sequences (`ESC [` ... final byte), OSC sequences
+/// (`ESC ]` ... `BEL` or `ESC \`), and other two `char` escapes.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct StripAnsi;
+
+impl Normalizer for StripAnsi {
+    type Output<I: Iterator<Item = Unit>> = Stripped<I>;
+
+    fn normalize<I: Iterator<Item = Unit>>(&self, units: I) -> Stripped<I> {
+        Stripped { source: units }
+    }
+}
+
+/// The units of [`StripAnsi`].
+#[derive(Clone, Debug)]
+pub struct Stripped<I> {
+    source: I,
+}
+
+const ESC: char = '\u{1B}';
+const BEL: char = '\u{7}';
+
+impl<I: Iterator<Item = Unit>> Iterator for Stripped<I> {
+    type Item = Unit;
+
+    fn next(&mut self) -> Option<Unit> {
+        loop {
+            let unit = self.source.next()?;
+            if unit.ch != ESC {
+                return Some(unit);
+            }
+
+            match self.source.next().map(|unit| unit.ch) {
+                Some('[') => {
+                    // Parameter and intermediate bytes, then a final byte.
+                    self.source
+                        .by_ref()
+                        .find(|unit| matches!(unit.ch, '\u{40}'..='\u{7E}'));
+                }
+                Some(']') => {
+                    let mut prev = None;
+                    self.source.by_ref().find(|unit| {
+                        let end = unit.ch == BEL || (prev == Some(ESC) && unit.ch == '\\');
+                        prev = Some(unit.ch);
+                        end
+                    });
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn normalized<'a>(
+        normalizer: &'a impl Normalizer,
+        s: &'a str,
+    ) -> impl Iterator<Item = char> + 'a {
+        normalizer.normalize(units(s)).map(|unit| unit.ch)
+    }
+
+    #[test]
+    fn spans_of_plain_units() {
+        let mut units = units("a\u{E9}");
+
+        assert_eq!(
+            Some(Unit {
+                ch: 'a',
+                start: 0,
+                end: 1
+            }),
+            units.next()
+        );
+        assert_eq!(
+            Some(Unit {
+                ch: '\u{E9}',
+                start: 1,
+                end: 3
+            }),
+            units.next()
+        );
+        assert_eq!(None, units.next());
+    }
+
+    #[test]
+    fn collapse_whitespace() {
+        assert!(normalized(&CollapseWhitespace, "a \t\n b  ").eq("a b ".chars()));
+
+        let collapsed: Option<Unit> = CollapseWhitespace.normalize(units("a \t b")).nth(1);
+        assert_eq!(
+            Some(Unit {
+                ch: ' ',
+                start: 1,
+                end: 4
+            }),
+            collapsed
+        );
+
+        assert_eq!(
+            "ls -l  ",
+            longest_common_prefix_with(&CollapseWhitespace, "ls -l  foo", "ls\t-l bar")
+        );
+        assert_eq!(
+            "a",
+            longest_common_prefix_with(&CollapseWhitespace, "a b", "ab")
+        );
+    }
+
+    #[test]
+    fn trim_start() {
+        assert!(normalized(&TrimStart, " \t a b ").eq("a b ".chars()));
+        assert!(normalized(&TrimStart, "   ").eq("".chars()));
+
+        assert_eq!(
+            "  ab",
+            longest_common_prefix_with(&TrimStart, "  abc", "abd")
+        );
+        assert_eq!("ab", longest_common_prefix_with(&TrimStart, "abc", "\tabd"));
+    }
+
+    #[test]
+    fn strip_ansi() {
+        assert!(normalized(&StripAnsi, "\x1b[1;31mred\x1b[0m").eq("red".chars()));
+        assert!(normalized(&StripAnsi, "\x1b]0;title\x07x").eq("x".chars()));
+        assert!(normalized(&StripAnsi, "\x1b]8;;url\x1b\\link").eq("link".chars()));
+        assert!(normalized(&StripAnsi, "a\x1bMb").eq("ab".chars()));
+
+        assert_eq!(
+            "\x1b[32mok\x1b[0m ",
+            longest_common_prefix_with(&StripAnsi, "\x1b[32mok\x1b[0m 1", "ok 2")
+        );
+        assert_eq!(
+            "ok ",
+            longest_common_prefix_with(&StripAnsi, "ok 2", "\x1b[32mok\x1b[0m 1")
+        );
+    }
+
+    #[test]
+    fn composition() {
+        let normalizer = CaseFold.then(Nfc).then(CollapseWhitespace);
+
+        assert_eq!(
+            "Caf\u{E9}  Au Lai",
+            longest_common_prefix_with(&normalizer, "Caf\u{E9}  Au Lait", "cafe\u{301} au\tlai")
+        );
+        assert_eq!(
+            "STRASSE ",
+            longest_common_prefix_with(&normalizer, "STRASSE 1", "stra\u{DF}e\t2")
+        );
+    }
+
+    #[test]
+    fn composition_order_does_not_matter_here() {
+        let a = "\x1b[1mHello\x1b[0m,   World";
+        let b = "hello, world!";
+
+        assert_eq!(
+            longest_common_prefix_with(&StripAnsi.then(CaseFold).then(CollapseWhitespace), a, b),
+            longest_common_prefix_with(&CollapseWhitespace.then(StripAnsi).then(CaseFold), a, b),
+        );
+        assert_eq!(
+            a,
+            longest_common_prefix_with(&StripAnsi.then(CaseFold).then(CollapseWhitespace), a, b)
+        );
+    }
+
+    #[test]
+    fn empty_iterable() {
+        let iter = [];
+
+        assert_eq!(None, longest_common_prefix_in_with(&CaseFold, iter));
+    }
+
+    #[test]
+    fn common_prefix_in_iterable() {
+        let iter = ["Hello  World", "hello world!", "HELLO\tWORLD?"];
+
+        assert_eq!(
+            Some("Hello  World"),
+            longest_common_prefix_in_with(&CaseFold.then(CollapseWhitespace), iter)
+        );
+    }
+}
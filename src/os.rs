@@ -0,0 +1,203 @@
+// Small library to find a common prefix among strings.
+// Copyright (C) 2024  Sohum Mendon
+// SPDX-License-Identifier: MIT
+
+//! Common prefixes of [`OsStr`]s and [`Path`]s, which may not be UTF-8.
+//!
+//! On Unix, these compare the raw bytes. Elsewhere, they compare
+//! [`OsStr::as_encoded_bytes`], and the result is moved back to the end
+//! of the previous UTF-8 `char`, since other splits aren't allowed there.
+
+use std::ffi::OsStr;
+use std::path::Path;
+
+use crate::kernel;
+
+/// Find the longest common prefix between two [`OsStr`]s.
+///
+/// This returns a prefix of `a`, which can be empty if there is no
+/// common prefix.
+///
+/// ```rust
+/// use std::ffi::OsStr;
+///
+/// use lcp::longest_common_prefix_os;
+///
+/// let (a, b) = (OsStr::new("report-2024.txt"), OsStr::new("report-2023.txt"));
+/// assert_eq!(OsStr::new("report-202"), longest_common_prefix_os(a, b));
+/// ```
+pub fn longest_common_prefix_os<'a>(a: &'a OsStr, b: &OsStr) -> &'a OsStr {
+    let len = kernel::mismatch(a.as_encoded_bytes(), b.as_encoded_bytes());
+
+    prefix(a, len)
+}
+
+/// Find the longest prefix in an iterable of [`OsStr`]s.
+///
+/// The items can be references to anything that is [`AsRef<OsStr>`], such
+/// as [`OsStr`], [`Path`] or [`str`].
+///
+/// This returns [`None`] if the passed in iterable is empty. Otherwise,
+/// it returns an [`OsStr`] (which is empty if there is no common prefix).
+pub fn longest_common_prefix_os_in<'a, S>(
+    iter: impl IntoIterator<Item = &'a S>,
+) -> Option<&'a OsStr>
+where
+    S: AsRef<OsStr> + ?Sized + 'a,
+{
+    let mut iter = iter.into_iter();
+
+    let mut lcp = iter.next()?.as_ref();
+
+    for cur in iter {
+        lcp = longest_common_prefix_os(lcp, cur.as_ref());
+
+        if lcp.is_empty() {
+            return Some(lcp);
+        }
+    }
+
+    Some(lcp)
+}
+
+/// Find the longest common prefix between two [`Path`]s, as strings.
+///
+/// Unlike [`longest_common_std_path`](crate::longest_common_std_path),
+/// this can end in the middle of a component.
+///
+/// ```rust
+/// use std::path::Path;
+///
+/// use lcp::longest_common_prefix_path;
+///
+/// let prefix = longest_common_prefix_path(Path::new("/usr/lib"), Path::new("/usr/libexec"));
+/// assert_eq!(Path::new("/usr/lib"), prefix);
+/// ```
+pub fn longest_common_prefix_path<'a>(a: &'a Path, b: &Path) -> &'a Path {
+    Path::new(longest_common_prefix_os(a.as_os_str(), b.as_os_str()))
+}
+
+/// Find the longest prefix in an iterable of [`Path`]s, as strings.
+///
+/// This returns [`None`] if the passed in iterable is empty. Otherwise,
+/// it returns a [`Path`] (which is empty if there is no common prefix).
+pub fn longest_common_prefix_path_in<'a, S>(
+    iter: impl IntoIterator<Item = &'a S>,
+) -> Option<&'a Path>
+where
+    S: AsRef<OsStr> + ?Sized + 'a,
+{
+    longest_common_prefix_os_in(iter).map(Path::new)
+}
+
+/// The first `len` bytes of `s`.
+#[cfg(unix)]
+pub(crate) fn prefix(s: &OsStr, len: usize) -> &OsStr {
+    use std::os::unix::ffi::OsStrExt;
+
+    OsStr::from_bytes(&s.as_bytes()[..len])
+}
+
+/// The first `len` bytes of `s`, moved back to the end of a UTF-8 `char`.
+#[cfg(not(unix))]
+pub(crate) fn prefix(s: &OsStr, mut len: usize) -> &OsStr {
+    let bytes = s.as_encoded_bytes();
+
+    if len == bytes.len() {
+        return s;
+    }
+
+    while len > 0 && !ends_with_utf8_char(&bytes[..len]) {
+        len -= 1;
+    }
+
+    // SAFETY: `bytes` came from `as_encoded_bytes`, and it is either split
+    // at the start, or right after a valid UTF-8 `char`.
+    unsafe { OsStr::from_encoded_bytes_unchecked(&bytes[..len]) }
+}
+
+#[cfg(not(unix))]
+fn ends_with_utf8_char(bytes: &[u8]) -> bool {
+    (1..=4.min(bytes.len())).any(|n| core::str::from_utf8(&bytes[bytes.len() - n..]).is_ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn utf8() {
+        let (a, b) = (OsStr::new("abc"), OsStr::new("abd"));
+
+        assert_eq!(OsStr::new("ab"), longest_common_prefix_os(a, b));
+        assert_eq!(a, longest_common_prefix_os(a, a));
+        assert_eq!(
+            OsStr::new(""),
+            longest_common_prefix_os(a, OsStr::new("xyz"))
+        );
+    }
+
+    #[test]
+    fn splits_multibyte_char_only_on_unix() {
+        // 'é' and 'è' share their first byte.
+        let prefix = longest_common_prefix_os(OsStr::new("h\u{E9}"), OsStr::new("h\u{E8}"));
+
+        if cfg!(unix) {
+            assert_eq!(b"h\xC3", prefix.as_encoded_bytes());
+        } else {
+            assert_eq!(OsStr::new("h"), prefix);
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn non_utf8() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let a = OsStr::from_bytes(b"file-\xFF\xFE-a");
+        let b = OsStr::from_bytes(b"file-\xFF\xFE-b");
+        let c = OsStr::from_bytes(b"file-\xFF\xFD");
+
+        assert_eq!(
+            OsStr::from_bytes(b"file-\xFF\xFE-"),
+            longest_common_prefix_os(a, b)
+        );
+        assert_eq!(
+            OsStr::from_bytes(b"file-\xFF"),
+            longest_common_prefix_os(a, c)
+        );
+        assert_eq!(
+            Some(OsStr::from_bytes(b"file-\xFF")),
+            longest_common_prefix_os_in([a, b, c])
+        );
+    }
+
+    #[test]
+    fn paths() {
+        assert_eq!(
+            Path::new("/usr/lib"),
+            longest_common_prefix_path(Path::new("/usr/lib"), Path::new("/usr/libexec"))
+        );
+        assert_eq!(
+            Some(Path::new("/usr/")),
+            longest_common_prefix_path_in([Path::new("/usr/lib"), Path::new("/usr/bin")])
+        );
+    }
+
+    #[test]
+    fn empty_iterable() {
+        let iter: [&OsStr; 0] = [];
+
+        assert_eq!(None, longest_common_prefix_os_in(iter));
+    }
+
+    #[test]
+    fn common_prefix_in_iterable() {
+        let iter = ["prefix-a", "prefix-b"];
+
+        assert_eq!(
+            Some(OsStr::new("prefix-")),
+            longest_common_prefix_os_in(iter)
+        );
+    }
+}
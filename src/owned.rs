@@ -0,0 +1,178 @@
+// Small library to find a common prefix among strings.
+// Copyright (C) 2024  Sohum Mendon
+// SPDX-License-Identifier: MIT
+
+//! Common prefixes of iterables that yield owned strings.
+//!
+//! [`longest_common_prefix_in_as_ref`](crate::longest_common_prefix_in_as_ref)
+//! borrows its result from the first item, so it needs references. When
+//! the items are owned, such as the output of a `map`, the result can't
+//! borrow from them, and is returned as a [`String`] or [`Cow`] instead.
+
+use alloc::borrow::{Cow, ToOwned};
+use alloc::string::String;
+
+use crate::common_prefix_len;
+
+/// Find the longest prefix in an iterable of owned strings.
+///
+/// The items can be anything that is [`AsRef<str>`], such as [`String`],
+/// `Box<str>` or `Rc<str>`. The result is copied out of the first item.
+///
+/// This returns [`None`] if the passed in iterable is empty. Otherwise,
+/// it returns a [`String`] (including the empty string `""` if there is
+/// no common prefix).
+///
+/// ```rust
+/// use lcp::longest_common_prefix_in_owned;
+///
+/// let names = ["foo.rs", "foo.txt"];
+/// let prefix = longest_common_prefix_in_owned(names.iter().map(|name| name.to_uppercase()));
+///
+/// assert_eq!(Some(String::from("FOO.")), prefix);
+/// ```
+pub fn longest_common_prefix_in_owned<S: AsRef<str>>(
+    iter: impl IntoIterator<Item = S>,
+) -> Option<String> {
+    let mut iter = iter.into_iter();
+
+    let first = iter.next()?;
+    let first = first.as_ref();
+
+    Some(first[..prefix_len(first, iter)].to_owned())
+}
+
+/// Find the longest prefix in an iterable of borrowed or owned strings.
+///
+/// The items can be anything that is [`Into<Cow<str>>`](Cow), so an
+/// iterable of [`Cow`]s can mix borrowed and owned strings. If the first
+/// item is borrowed, so is the result. If it is owned, it is shortened in
+/// place, so this never allocates.
+///
+/// This returns [`None`] if the passed in iterable is empty. Otherwise,
+/// it returns a [`Cow`] (including the empty string `""` if there is no
+/// common prefix).
+///
+/// ```rust
+/// use std::borrow::Cow;
+///
+/// use lcp::longest_common_prefix_in_cow;
+///
+/// let items = [Cow::Borrowed("hello world"), Cow::Owned(String::from("hello there"))];
+/// let prefix = longest_common_prefix_in_cow(items);
+///
+/// assert_eq!(Some(Cow::Borrowed("hello ")), prefix);
+/// ```
+pub fn longest_common_prefix_in_cow<'a, S: Into<Cow<'a, str>>>(
+    iter: impl IntoIterator<Item = S>,
+) -> Option<Cow<'a, str>> {
+    let mut iter = iter.into_iter();
+
+    let first = iter.next()?.into();
+    let len = prefix_len(&first, iter.map(Into::into));
+
+    Some(match first {
+        Cow::Borrowed(first) => Cow::Borrowed(&first[..len]),
+        Cow::Owned(mut first) => {
+            first.truncate(len);
+            Cow::Owned(first)
+        }
+    })
+}
+
+/// Find the length of the longest prefix of `first` that every string in
+/// `rest` shares.
+fn prefix_len<S: AsRef<str>>(first: &str, rest: impl IntoIterator<Item = S>) -> usize {
+    let mut len = first.len();
+
+    for cur in rest {
+        len = common_prefix_len(&first[..len], cur.as_ref());
+
+        if len == 0 {
+            return len;
+        }
+    }
+
+    len
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::boxed::Box;
+    use alloc::rc::Rc;
+    use alloc::vec;
+
+    #[test]
+    fn owned_items() {
+        let strings = vec![String::from("prefix-a"), String::from("prefix-b")];
+
+        assert_eq!(
+            Some(String::from("prefix-")),
+            longest_common_prefix_in_owned(strings)
+        );
+
+        let boxes: [Box<str>; 2] = ["abc".into(), "abd".into()];
+
+        assert_eq!(
+            Some(String::from("ab")),
+            longest_common_prefix_in_owned(boxes)
+        );
+
+        let rcs: [Rc<str>; 2] = ["abc".into(), "xyz".into()];
+
+        assert_eq!(Some(String::new()), longest_common_prefix_in_owned(rcs));
+    }
+
+    #[test]
+    fn borrowed_items() {
+        assert_eq!(
+            Some(String::from("he")),
+            longest_common_prefix_in_owned(["hello", "help", "hex"])
+        );
+        assert_eq!(
+            Some(Cow::Borrowed("he")),
+            longest_common_prefix_in_cow(["hello", "help", "hex"])
+        );
+    }
+
+    #[test]
+    fn cow_keeps_ownership_of_first() {
+        let items = [
+            Cow::Owned(String::from("hello world")),
+            Cow::Borrowed("hello there"),
+        ];
+        let prefix = longest_common_prefix_in_cow(items).unwrap();
+
+        assert!(matches!(prefix, Cow::Owned(_)));
+        assert_eq!("hello ", prefix);
+
+        let items = [
+            Cow::Borrowed("hello world"),
+            Cow::Owned(String::from("help")),
+        ];
+        let prefix = longest_common_prefix_in_cow(items).unwrap();
+
+        assert!(matches!(prefix, Cow::Borrowed(_)));
+        assert_eq!("hel", prefix);
+    }
+
+    #[test]
+    fn owned_strings_into_cow() {
+        let strings = vec![String::from("h\u{E9}llo"), String::from("h\u{E8}llo")];
+
+        assert_eq!(
+            Some(Cow::Owned(String::from("h"))),
+            longest_common_prefix_in_cow(strings)
+        );
+    }
+
+    #[test]
+    fn empty_iterable() {
+        let iter: [String; 0] = [];
+
+        assert_eq!(None, longest_common_prefix_in_owned(iter.clone()));
+        assert_eq!(None, longest_common_prefix_in_cow(iter));
+    }
+}
@@ -0,0 +1,811 @@
+// Small library to find a common prefix among strings.
+// Copyright (C) 2024  Sohum Mendon
+// SPDX-License-Identifier: MIT
+
+//! Common prefixes of paths, made of whole components.
+//!
+//! This follows Python's `os.path.commonpath`: repeated separators and
+//! `.` components are ignored, and `..` is compared like any other name.
+//!
+//! Windows paths are handled as plain strings, so they give the same
+//! results on every host.
+
+use core::ops::Range;
+
+use crate::normalize::{self, Normalizer, SimpleCaseFold};
+
+/// Find the longest common path between two `/`-separated paths.
+///
+/// Unlike [`longest_common_prefix`](crate::longest_common_prefix), this
+/// only cuts at separators, so `/usr/lib` and `/usr/libexec` share `/usr`.
+/// The result is a slice of `a`, so it keeps any repeated separators or
+/// `.` components that `a` had before the cut.
+///
+/// An absolute path and a relative path have no common path, so this
+/// returns the empty string `""` for them.
+///
+/// ```rust
+/// use lcp::longest_common_path;
+///
+/// assert_eq!("/usr", longest_common_path("/usr/lib", "/usr/libexec"));
+/// assert_eq!("/usr//lib", longest_common_path("/usr//lib/x", "/usr/./lib/y"));
+/// assert_eq!("/", longest_common_path("/etc", "/usr"));
+/// assert_eq!("", longest_common_path("/usr", "usr"));
+/// ```
+pub fn longest_common_path<'a>(a: &'a str, b: &str) -> &'a str {
+    // The cut is at a separator, which is a `char` boundary.
+    &a[..longest_common_path_bytes(a.as_bytes(), b.as_bytes()).len()]
+}
+
+/// Find the longest common path in an iterable of `/`-separated paths.
+///
+/// This returns [`None`] if the passed in iterable is empty. Otherwise,
+/// it returns a prefix of the first path (including the empty string `""`
+/// if there is no common path).
+pub fn longest_common_path_in<'a>(iter: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    let mut iter = iter.into_iter();
+
+    // Compare the first path with itself, so that a single path is cut
+    // the same way as several.
+    let first = iter.next()?;
+    let mut lcp = longest_common_path(first, first);
+
+    for cur in iter {
+        lcp = longest_common_path(lcp, cur);
+
+        if lcp.is_empty() {
+            return Some(lcp);
+        }
+    }
+
+    Some(lcp)
+}
+
+/// Find the longest common path between two `/`-separated paths that may
+/// not be UTF-8, such as Unix file names.
+///
+/// This works like [`longest_common_path`], and compares components as
+/// bytes.
+///
+/// ```rust
+/// use lcp::longest_common_path_bytes;
+///
+/// assert_eq!(b"/usr/\xFF", longest_common_path_bytes(b"/usr/\xFF/a", b"/usr/\xFF/b"));
+/// assert_eq!(b"/usr", longest_common_path_bytes(b"/usr/\xFF", b"/usr/\xFE"));
+/// ```
+pub fn longest_common_path_bytes<'a>(a: &'a [u8], b: &[u8]) -> &'a [u8] {
+    let absolute = a.starts_with(b"/");
+    if absolute != b.starts_with(b"/") {
+        return &[];
+    }
+
+    let len = common_components(
+        (a, usize::from(absolute)),
+        (b, usize::from(absolute)),
+        |byte| byte == b'/',
+        |x, y| a[x] == b[y],
+    );
+
+    &a[..len]
+}
+
+/// Find the longest common path in an iterable of `/`-separated paths that
+/// may not be UTF-8.
+///
+/// This returns [`None`] if the passed in iterable is empty. Otherwise,
+/// it returns a prefix of the first path (which is empty if there is no
+/// common path).
+pub fn longest_common_path_bytes_in<'a>(
+    iter: impl IntoIterator<Item = &'a [u8]>,
+) -> Option<&'a [u8]> {
+    let mut iter = iter.into_iter();
+
+    let first = iter.next()?;
+    let mut lcp = longest_common_path_bytes(first, first);
+
+    for cur in iter {
+        lcp = longest_common_path_bytes(lcp, cur);
+
+        if lcp.is_empty() {
+            return Some(lcp);
+        }
+    }
+
+    Some(lcp)
+}
+
+/// Find the longest common path between two Windows paths.
+///
+/// Both `\\` and `/` are separators. A drive (`C:`) or UNC share
+/// (`\\server\share`) at the start of a path is compared as a whole,
+/// and paths with different drives or shares have no common path.
+/// Otherwise, this works like [`longest_common_path`], and returns a slice
+/// of `a`.
+///
+/// A verbatim path (`\\?\C:\` or `\\?\UNC\server\share\`) has the same
+/// root as the drive or share it names, and a device path (`\\.\COM1`) is
+/// rooted at its device. The rest of a verbatim path is split like any
+/// other, even though Windows wouldn't treat `/` as a separator there.
+///
+/// Everything is compared case-insensitively, one `char` at a time as
+/// Windows does, so `ß` doesn't match `SS`.
+///
+/// ```rust
+/// use lcp::longest_common_windows_path;
+///
+/// let path = longest_common_windows_path(r"C:\Program Files\App", r"c:/program files/Other");
+/// assert_eq!(r"C:\Program Files", path);
+///
+/// let path = longest_common_windows_path(r"\\srv\share\a", r"\\SRV\Share\b");
+/// assert_eq!(r"\\srv\share\", path);
+///
+/// let path = longest_common_windows_path(r"C:\Windows", r"D:\Windows");
+/// assert_eq!("", path);
+///
+/// let path = longest_common_windows_path(r"\\?\C:\Users\a", r"C:\Users\b");
+/// assert_eq!(r"\\?\C:\Users", path);
+/// ```
+pub fn longest_common_windows_path<'a>(a: &'a str, b: &str) -> &'a str {
+    let (a_prefix, a_root) = windows_root(a);
+    let (b_prefix, b_root) = windows_root(b);
+
+    if !a_prefix.same_as(b_prefix) || a_prefix.is_rooted(a_root) != b_prefix.is_rooted(b_root) {
+        return "";
+    }
+
+    let len = common_components(
+        (a.as_bytes(), a_root.len),
+        (b.as_bytes(), b_root.len),
+        is_windows_separator,
+        |x, y| eq_ignore_case(&a[x], &b[y]),
+    );
+
+    &a[..len]
+}
+
+/// Find the longest common path in an iterable of Windows paths.
+///
+/// This returns [`None`] if the passed in iterable is empty. Otherwise,
+/// it returns a prefix of the first path (including the empty string `""`
+/// if there is no common path).
+pub fn longest_common_windows_path_in<'a>(
+    iter: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+    let mut iter = iter.into_iter();
+
+    let first = iter.next()?;
+    let mut lcp = longest_common_windows_path(first, first);
+
+    for cur in iter {
+        lcp = longest_common_windows_path(lcp, cur);
+
+        if lcp.is_empty() {
+            return Some(lcp);
+        }
+    }
+
+    Some(lcp)
+}
+
+/// Find the byte length of the common components of two paths. Each path
+/// comes with the length of its root, which is not split into components.
+/// Components are compared by their byte ranges. If no component is
+/// shared, the result is the length of `a`'s root.
+///
+/// Separators are ASCII, so every range is on `char` boundaries if the
+/// paths are UTF-8.
+fn common_components(
+    (a, a_root): (&[u8], usize),
+    (b, b_root): (&[u8], usize),
+    is_separator: fn(u8) -> bool,
+    eq: impl Fn(Range<usize>, Range<usize>) -> bool,
+) -> usize {
+    let mut len = a_root;
+
+    for (x, y) in components(a, a_root, is_separator).zip(components(b, b_root, is_separator)) {
+        if !eq(x.clone(), y) {
+            break;
+        }
+
+        len = x.end;
+    }
+
+    len
+}
+
+/// The byte range of every component of `path` after `root`, skipping
+/// empty and `.` components.
+fn components(
+    path: &[u8],
+    root: usize,
+    is_separator: fn(u8) -> bool,
+) -> impl Iterator<Item = Range<usize>> + '_ {
+    path[root..]
+        .split(move |&byte| is_separator(byte))
+        .scan(root, |start, component| {
+            let range = *start..*start + component.len();
+            *start = range.end + 1;
+            Some(range)
+        })
+        .filter(move |range| !matches!(&path[range.clone()], b"" | b"."))
+}
+
+fn is_windows_separator(byte: u8) -> bool {
+    byte == b'\\' || byte == b'/'
+}
+
+/// The drive, UNC share or device at the start of a Windows path.
+#[derive(Clone, Copy)]
+enum Prefix<'a> {
+    None,
+    /// `C:`, or `\\?\C:` if `verbatim`.
+    Disk {
+        letter: u8,
+        verbatim: bool,
+    },
+    /// `\\server\share`, or `\\?\UNC\server\share`. The server and share
+    /// may be missing.
+    Unc {
+        server: &'a str,
+        share: &'a str,
+    },
+    /// `\\.\device`.
+    Device(&'a str),
+    /// `\\?\name`, for any other name.
+    Verbatim(&'a str),
+}
+
+impl Prefix<'_> {
+    /// Whether both prefixes name the same drive, share or device.
+    fn same_as(self, other: Prefix<'_>) -> bool {
+        match (self, other) {
+            (Prefix::None, Prefix::None) => true,
+            (Prefix::Disk { letter: x, .. }, Prefix::Disk { letter: y, .. }) => {
+                x.eq_ignore_ascii_case(&y)
+            }
+            (
+                Prefix::Unc { server, share },
+                Prefix::Unc {
+                    server: other_server,
+                    share: other_share,
+                },
+            ) => eq_ignore_case(server, other_server) && eq_ignore_case(share, other_share),
+            (Prefix::Device(x), Prefix::Device(y)) | (Prefix::Verbatim(x), Prefix::Verbatim(y)) => {
+                eq_ignore_case(x, y)
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether a path with this prefix is rooted. Only a plain drive or no
+    /// prefix needs a separator after it.
+    fn is_rooted(self, root: Root) -> bool {
+        match self {
+            Prefix::None
+            | Prefix::Disk {
+                verbatim: false, ..
+            } => root.len > root.prefix,
+            _ => true,
+        }
+    }
+}
+
+/// The byte length of the prefix of a Windows path, and of the prefix
+/// plus the root separator (which is the same if there is none).
+#[derive(Clone, Copy)]
+struct Root {
+    prefix: usize,
+    len: usize,
+}
+
+/// Split a Windows path into its drive, UNC share or device, and its root
+/// separator.
+fn windows_root(path: &str) -> (Prefix<'_>, Root) {
+    let bytes = path.as_bytes();
+    let is_separator = |i: usize| bytes.get(i).copied().is_some_and(is_windows_separator);
+    let is_drive = |i: usize| {
+        bytes.get(i).is_some_and(u8::is_ascii_alphabetic) && bytes.get(i + 1) == Some(&b':')
+    };
+    // The end of the component that starts at `start`.
+    let end = |start: usize, is_separator: fn(u8) -> bool| {
+        bytes[start..]
+            .iter()
+            .position(|&byte| is_separator(byte))
+            .map_or(path.len(), |i| start + i)
+    };
+    // A server and share that start at `start`, which may be missing.
+    let unc = |start: usize, is_separator: fn(u8) -> bool| {
+        let server = end(start, is_separator);
+        let share = if server == path.len() {
+            server
+        } else {
+            end(server + 1, is_separator)
+        };
+        let prefix = Prefix::Unc {
+            server: &path[start..server],
+            share: &path[(server + 1).min(share)..share],
+        };
+        (prefix, share)
+    };
+
+    let (prefix, len) = if path.starts_with(r"\\?\") {
+        // Only `\` is a separator in the prefix of a verbatim path.
+        let is_backslash = |byte| byte == b'\\';
+        if path
+            .get(4..8)
+            .is_some_and(|unc| unc.eq_ignore_ascii_case(r"UNC\"))
+        {
+            unc(8, is_backslash)
+        } else if is_drive(4) {
+            let letter = bytes[4];
+            (
+                Prefix::Disk {
+                    letter,
+                    verbatim: true,
+                },
+                6,
+            )
+        } else {
+            let name = end(4, is_backslash);
+            (Prefix::Verbatim(&path[4..name]), name)
+        }
+    } else if is_separator(0) && is_separator(1) && bytes.get(2) == Some(&b'.') && is_separator(3) {
+        let device = end(4, is_windows_separator);
+        (Prefix::Device(&path[4..device]), device)
+    } else if is_separator(0) && is_separator(1) {
+        unc(2, is_windows_separator)
+    } else if is_drive(0) {
+        let letter = bytes[0];
+        (
+            Prefix::Disk {
+                letter,
+                verbatim: false,
+            },
+            2,
+        )
+    } else {
+        (Prefix::None, 0)
+    };
+
+    let root = Root {
+        prefix: len,
+        len: len + usize::from(is_separator(len)),
+    };
+
+    (prefix, root)
+}
+
+/// Compare two path components (or prefixes), ignoring case and which
+/// separator is used.
+fn eq_ignore_case(a: &str, b: &str) -> bool {
+    let folded = |s| {
+        SimpleCaseFold.normalize(normalize::units(s)).map(|unit| {
+            if unit.ch == '/' {
+                '\\'
+            } else {
+                unit.ch
+            }
+        })
+    };
+
+    folded(a).eq(folded(b))
+}
+
+#[cfg(feature = "std")]
+pub use self::std_path::{longest_common_std_path, longest_common_std_path_in};
+
+#[cfg(feature = "std")]
+pub(crate) mod std_path {
+    use std::path::{Component, Path};
+
+    /// Find the longest common path between two [`Path`]s.
+    ///
+    /// This compares [`Path::components`], so it uses the separators and
+    /// prefixes of the host platform. The result is an ancestor of `a`.
+    ///
+    /// ```rust
+    /// use std::path::Path;
+    ///
+    /// use lcp::longest_common_std_path;
+    ///
+    /// let path = longest_common_std_path(Path::new("/usr/lib"), Path::new("/usr/libexec"));
+    /// assert_eq!(Path::new("/usr"), path);
+    /// ```
+    pub fn longest_common_std_path<'a>(a: &'a Path, b: &Path) -> &'a Path {
+        ancestor(a, common_components(a, b))
+    }
+
+    /// Find the longest common path in an iterable of [`Path`]s.
+    ///
+    /// This returns [`None`] if the passed in iterable is empty. Otherwise,
+    /// it returns an ancestor of the first path (including the empty path
+    /// if there is no common path).
+    pub fn longest_common_std_path_in<'a>(
+        iter: impl IntoIterator<Item = &'a Path>,
+    ) -> Option<&'a Path> {
+        let mut iter = iter.into_iter();
+
+        let mut lcp = iter.next()?;
+
+        for cur in iter {
+            lcp = longest_common_std_path(lcp, cur);
+
+            if lcp.as_os_str().is_empty() {
+                return Some(lcp);
+            }
+        }
+
+        Some(lcp)
+    }
+
+    /// The number of leading components that `a` and `b` share.
+    pub(crate) fn common_components(a: &Path, b: &Path) -> usize {
+        components(a)
+            .zip(components(b))
+            .take_while(|(x, y)| x == y)
+            .count()
+    }
+
+    /// The longest ancestor of `path` with at most `count` components.
+    pub(crate) fn ancestor(path: &Path, count: usize) -> &Path {
+        path.ancestors()
+            .find(|p| components(p).count() <= count)
+            .unwrap_or(Path::new(""))
+    }
+
+    fn components(path: &Path) -> impl Iterator<Item = Component<'_>> {
+        path.components().filter(|c| *c != Component::CurDir)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cuts_at_separators() {
+        assert_eq!("/usr", longest_common_path("/usr/lib", "/usr/libexec"));
+        assert_eq!("/usr/lib", longest_common_path("/usr/lib", "/usr/lib/x"));
+        assert_eq!("/usr/lib", longest_common_path("/usr/lib/x", "/usr/lib"));
+        assert_eq!("a/b", longest_common_path("a/b/c", "a/b/d"));
+    }
+
+    #[test]
+    fn same_path() {
+        assert_eq!("/usr/lib", longest_common_path("/usr/lib", "/usr/lib"));
+        assert_eq!("/usr/lib", longest_common_path("/usr/lib", "/usr/lib/"));
+        assert_eq!("/usr/lib", longest_common_path("/usr/lib/", "/usr/lib"));
+    }
+
+    #[test]
+    fn same_string_twice() {
+        let path = "/usr/lib/";
+        let bytes = *b"/usr/lib/";
+        let copy = core::str::from_utf8(&bytes).unwrap();
+
+        assert_eq!(
+            longest_common_path(path, copy),
+            longest_common_path(path, path)
+        );
+        assert_eq!("/usr/lib", longest_common_path(path, path));
+        assert_eq!(Some("/usr/lib"), longest_common_path_in([path]));
+        assert_eq!(Some("/"), longest_common_path_in(["//"]));
+    }
+
+    #[test]
+    fn root() {
+        assert_eq!("/", longest_common_path("/etc", "/usr"));
+        assert_eq!("/", longest_common_path("/", "/usr"));
+        assert_eq!("/", longest_common_path("//", "/"));
+    }
+
+    #[test]
+    fn no_common_path() {
+        assert_eq!("", longest_common_path("etc", "usr"));
+        assert_eq!("", longest_common_path("", "usr"));
+        assert_eq!("", longest_common_path("/usr", "usr"));
+        assert_eq!("", longest_common_path("usr", "/usr"));
+    }
+
+    #[test]
+    fn repeated_separators() {
+        assert_eq!(
+            "/usr//lib",
+            longest_common_path("/usr//lib/x", "/usr/lib/y")
+        );
+        assert_eq!(
+            "//usr/lib",
+            longest_common_path("//usr/lib/x", "/usr///lib/y")
+        );
+    }
+
+    #[test]
+    fn dot_components() {
+        assert_eq!("./a", longest_common_path("./a/b", "a/c"));
+        assert_eq!("a/./b", longest_common_path("a/./b/c", "a/b/./d"));
+        assert_eq!("", longest_common_path(".", "a"));
+    }
+
+    #[test]
+    fn dot_dot_components() {
+        assert_eq!("../a", longest_common_path("../a/b", "../a/c"));
+        assert_eq!("a", longest_common_path("a/../b", "a/b"));
+    }
+
+    #[test]
+    fn multi_byte() {
+        assert_eq!(
+            "/données",
+            longest_common_path("/données/été", "/données/étés")
+        );
+    }
+
+    #[test]
+    fn non_utf8() {
+        let (a, b): (&[u8], &[u8]) = (b"/usr/\xFF/a", b"/usr/\xFF/b");
+
+        assert_eq!(b"/usr/\xFF", longest_common_path_bytes(a, b));
+        assert_eq!(b"/usr", longest_common_path_bytes(a, b"/usr/\xFE/a"));
+        assert_eq!(b"", longest_common_path_bytes(a, b"usr/\xFF"));
+        assert_eq!(b"/", longest_common_path_bytes(b"/\xFF", b"/\xFE"));
+        assert_eq!(b"\xFF", longest_common_path_bytes(b"\xFF//./a", b"\xFF/b"));
+    }
+
+    #[test]
+    fn same_as_bytes() {
+        let pairs = [
+            ("/usr/lib", "/usr/libexec"),
+            ("/usr//lib/x", "/usr/./lib/y"),
+            ("/etc", "/usr"),
+            ("/usr", "usr"),
+            ("./a/b", "a/c"),
+            ("/donn\u{E9}es/\u{E9}t\u{E9}", "/donn\u{E9}es/\u{E9}t\u{E8}"),
+        ];
+
+        for (a, b) in pairs {
+            assert_eq!(
+                longest_common_path(a, b).as_bytes(),
+                longest_common_path_bytes(a.as_bytes(), b.as_bytes())
+            );
+        }
+    }
+
+    #[test]
+    fn empty_iterable() {
+        let iter = [];
+
+        assert_eq!(None, longest_common_path_in(iter));
+        assert_eq!(None, longest_common_path_bytes_in([]));
+    }
+
+    #[test]
+    fn common_path_in_iterable() {
+        let iter = ["/usr/lib/x", "/usr/libexec/y", "/usr/local/lib"];
+
+        assert_eq!(Some("/usr"), longest_common_path_in(iter));
+
+        let iter = ["/usr/lib", "/etc", "usr"];
+
+        assert_eq!(Some(""), longest_common_path_in(iter));
+
+        let iter: [&[u8]; 3] = [b"/srv/\xFF/a", b"/srv/\xFF/b/c", b"/srv/\xFF//d"];
+
+        assert_eq!(Some(&b"/srv/\xFF"[..]), longest_common_path_bytes_in(iter));
+        assert_eq!(
+            Some(&b"/srv"[..]),
+            longest_common_path_bytes_in([&b"/srv/"[..]])
+        );
+    }
+
+    #[test]
+    fn windows_components() {
+        assert_eq!(
+            r"C:\Program Files",
+            longest_common_windows_path(r"C:\Program Files\App", r"C:\Program Files\Other")
+        );
+        assert_eq!(
+            r"C:\",
+            longest_common_windows_path(r"C:\Users\a", r"C:\Users2\a")
+        );
+        assert_eq!(r"a\b", longest_common_windows_path(r"a\b\c", r"a\b\d"));
+    }
+
+    #[test]
+    fn windows_separators() {
+        assert_eq!(
+            r"C:\a/b",
+            longest_common_windows_path(r"C:\a/b\c", r"C:/a\b/d")
+        );
+        assert_eq!(
+            r"C:\\a",
+            longest_common_windows_path(r"C:\\a\.\b", r"C:\a\c")
+        );
+    }
+
+    #[test]
+    fn windows_case_insensitive() {
+        assert_eq!(
+            r"C:\Program Files",
+            longest_common_windows_path(r"C:\Program Files\a", r"c:\PROGRAM FILES\b")
+        );
+        // Windows maps case one `char` at a time, so 'ß' is not "SS".
+        assert_eq!(
+            r"C:\",
+            longest_common_windows_path(r"C:\Straße\a", r"C:\STRASSE\b")
+        );
+        assert_eq!(
+            r"C:\Straße",
+            longest_common_windows_path(r"C:\Straße\a", r"C:\STRAẞE\b")
+        );
+    }
+
+    #[test]
+    fn windows_drives() {
+        assert_eq!(
+            r"C:\",
+            longest_common_windows_path(r"C:\Windows", r"C:\Users")
+        );
+        assert_eq!(
+            "",
+            longest_common_windows_path(r"C:\Windows", r"D:\Windows")
+        );
+        // A drive-relative path and a rooted one don't mix.
+        assert_eq!("", longest_common_windows_path(r"C:Windows", r"C:\Windows"));
+        assert_eq!("C:", longest_common_windows_path(r"C:a", r"C:b"));
+        assert_eq!("", longest_common_windows_path(r"C:\a", r"\a"));
+        assert_eq!(r"\", longest_common_windows_path(r"\a", r"/b"));
+    }
+
+    #[test]
+    fn windows_unc() {
+        assert_eq!(
+            r"\\srv\share\dir",
+            longest_common_windows_path(r"\\srv\share\dir\a", r"\\SRV\SHARE\dir\b")
+        );
+        assert_eq!(
+            r"\\srv\share\",
+            longest_common_windows_path(r"\\srv\share\a", r"//srv/share/b")
+        );
+        assert_eq!(
+            r"\\srv\share",
+            longest_common_windows_path(r"\\srv\share", r"\\srv\share\b")
+        );
+        // The share is compared as a whole.
+        assert_eq!(
+            "",
+            longest_common_windows_path(r"\\srv\share\a", r"\\srv\share2\a")
+        );
+        assert_eq!(
+            "",
+            longest_common_windows_path(r"\\srv\share\a", r"\\srv2\share\a")
+        );
+        assert_eq!(
+            "",
+            longest_common_windows_path(r"\\srv\share\a", r"C:\share\a")
+        );
+    }
+
+    #[test]
+    fn windows_verbatim() {
+        assert_eq!(
+            r"\\?\C:\",
+            longest_common_windows_path(r"\\?\C:\a\x", r"\\?\C:\b\y")
+        );
+        assert_eq!(
+            "",
+            longest_common_windows_path(r"\\?\C:\a\x", r"\\?\D:\a\x")
+        );
+        // A verbatim drive or share is the same as the plain one.
+        assert_eq!(
+            r"C:\a",
+            longest_common_windows_path(r"C:\a\x", r"\\?\C:\a\y")
+        );
+        assert_eq!(
+            r"\\?\c:\a",
+            longest_common_windows_path(r"\\?\c:\a\x", r"C:/a/y")
+        );
+        assert_eq!(
+            r"\\?\UNC\srv\share\a",
+            longest_common_windows_path(r"\\?\UNC\srv\share\a\x", r"\\SRV\share\a\y")
+        );
+        // A verbatim drive is always rooted.
+        assert_eq!("", longest_common_windows_path(r"\\?\C:\a", r"C:a"));
+        assert_eq!(
+            r"\\?\Volume{1}\a",
+            longest_common_windows_path(r"\\?\Volume{1}\a\x", r"\\?\volume{1}\a\y")
+        );
+        assert_eq!(
+            "",
+            longest_common_windows_path(r"\\?\Volume{1}\a", r"\\?\Volume{2}\a")
+        );
+        assert_eq!(
+            "\\\\?\\ab\u{20AC}\\",
+            longest_common_windows_path("\\\\?\\ab\u{20AC}\\x", "\\\\?\\AB\u{20AC}\\y")
+        );
+        // `//?/` is not verbatim, so it is a share named `C:` on a server
+        // named `?`.
+        assert_eq!("", longest_common_windows_path(r"//?/C:/a", r"C:\a"));
+    }
+
+    #[test]
+    fn windows_devices() {
+        assert_eq!(
+            r"\\.\pipe\a",
+            longest_common_windows_path(r"\\.\pipe\a\x", r"//./PIPE/a/y")
+        );
+        assert_eq!("", longest_common_windows_path(r"\\.\COM1", r"\\.\COM2"));
+        // A device is not a UNC server named `.`.
+        assert_eq!(
+            "",
+            longest_common_windows_path(r"\\.\pipe\a", r"\\..\pipe\a")
+        );
+    }
+
+    #[test]
+    fn windows_same_string_twice() {
+        let path = r"C:\a\";
+        let bytes = *br"C:\a\";
+        let copy = core::str::from_utf8(&bytes).unwrap();
+
+        assert_eq!(
+            longest_common_windows_path(path, copy),
+            longest_common_windows_path(path, path)
+        );
+        assert_eq!(r"C:\a", longest_common_windows_path(path, path));
+        assert_eq!(Some(r"C:\a"), longest_common_windows_path_in([path]));
+        assert_eq!(Some(r"C:\"), longest_common_windows_path_in([r"C:\"]));
+    }
+
+    #[test]
+    fn windows_common_path_in_iterable() {
+        let iter = [
+            r"C:\Users\me\a.txt",
+            r"c:/users/me/b.txt",
+            r"C:\USERS\Me\c\d.txt",
+        ];
+
+        assert_eq!(Some(r"C:\Users\me"), longest_common_windows_path_in(iter));
+
+        let iter = [r"C:\Users", r"D:\Users"];
+
+        assert_eq!(Some(""), longest_common_windows_path_in(iter));
+        assert_eq!(None, longest_common_windows_path_in([]));
+    }
+
+    #[cfg(feature = "std")]
+    mod std_path {
+        use std::path::Path;
+
+        use super::super::*;
+
+        fn lcp<'a>(a: &'a str, b: &'a str) -> &'a Path {
+            longest_common_std_path(Path::new(a), Path::new(b))
+        }
+
+        #[test]
+        fn cuts_at_separators() {
+            assert_eq!(Path::new("/usr"), lcp("/usr/lib", "/usr/libexec"));
+            assert_eq!(Path::new("/usr/lib"), lcp("/usr//lib/x", "/usr/./lib/y"));
+            assert_eq!(Path::new("a/b"), lcp("a/b/c", "a/b"));
+        }
+
+        #[test]
+        fn root() {
+            assert_eq!(Path::new("/"), lcp("/etc", "/usr"));
+        }
+
+        #[test]
+        fn no_common_path() {
+            assert_eq!(Path::new(""), lcp("etc", "usr"));
+            assert_eq!(Path::new(""), lcp("/usr", "usr"));
+        }
+
+        #[test]
+        fn common_path_in_iterable() {
+            let iter = ["/usr/lib/x", "/usr/libexec/y", "/usr/local/lib"].map(Path::new);
+
+            assert_eq!(Some(Path::new("/usr")), longest_common_std_path_in(iter));
+            assert_eq!(None, longest_common_std_path_in([]));
+        }
+    }
+}
@@ -0,0 +1,167 @@
+// Small library to find a common prefix among strings.
+// Copyright (C) 2024  Sohum Mendon
+// SPDX-License-Identifier: MIT
+
+//! Common prefixes of arbitrary slices.
+
+use core::ptr;
+
+use crate::kernel;
+
+/// Find the longest common prefix between two slices.
+///
+/// This returns a slice, which can be empty if there is no common prefix.
+/// For byte slices, [`longest_common_prefix_bytes`] is faster.
+pub fn longest_common_prefix_slice<'a, T: PartialEq>(a: &'a [T], b: &[T]) -> &'a [T] {
+    if ptr::eq(a, b) {
+        return a;
+    }
+
+    let len = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+
+    &a[..len]
+}
+
+/// Find the longest prefix in an iterable of slices.
+///
+/// This returns [`None`] if the passed in iterable is empty. Otherwise,
+/// it returns a slice (which is empty if there is no common prefix).
+pub fn longest_common_prefix_slice_in<'a, T: PartialEq + 'a>(
+    iter: impl IntoIterator<Item = &'a [T]>,
+) -> Option<&'a [T]> {
+    let mut iter = iter.into_iter();
+
+    let mut lcp = iter.next()?;
+
+    for cur in iter {
+        lcp = longest_common_prefix_slice(lcp, cur);
+
+        if lcp.is_empty() {
+            return Some(lcp);
+        }
+    }
+
+    Some(lcp)
+}
+
+/// Find the longest common prefix between two byte slices.
+///
+/// This returns the same slice as [`longest_common_prefix_slice`], but
+/// compares the bytes with the same word-at-a-time and SIMD kernel as
+/// [`longest_common_prefix`](crate::longest_common_prefix). Unlike that
+/// function, the result can end in the middle of a UTF-8 `char`.
+///
+/// ```rust
+/// use lcp::longest_common_prefix_bytes;
+///
+/// assert_eq!(b"abc\xFF", longest_common_prefix_bytes(b"abc\xFF\xFE", b"abc\xFF\xFD"));
+/// ```
+pub fn longest_common_prefix_bytes<'a>(a: &'a [u8], b: &[u8]) -> &'a [u8] {
+    &a[..kernel::mismatch(a, b)]
+}
+
+/// Find the longest prefix in an iterable of byte slices.
+///
+/// This returns [`None`] if the passed in iterable is empty. Otherwise,
+/// it returns a slice (which is empty if there is no common prefix).
+pub fn longest_common_prefix_bytes_in<'a>(
+    iter: impl IntoIterator<Item = &'a [u8]>,
+) -> Option<&'a [u8]> {
+    let mut iter = iter.into_iter();
+
+    let mut lcp = iter.next()?;
+
+    for cur in iter {
+        lcp = longest_common_prefix_bytes(lcp, cur);
+
+        if lcp.is_empty() {
+            return Some(lcp);
+        }
+    }
+
+    Some(lcp)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bytes() {
+        assert_eq!(b"abc", longest_common_prefix_slice(b"abcd", b"abcx"));
+        assert_eq!(b"", longest_common_prefix_slice(b"abc", b"xyz"));
+        assert_eq!(b"ab", longest_common_prefix_slice(b"ab", b"abc"));
+        assert_eq!(b"ab", longest_common_prefix_slice(b"abc", b"ab"));
+
+        // Unlike strings, bytes can be split in the middle of a `char`.
+        let (a, b) = ("h\u{E9}".as_bytes(), "h\u{E8}".as_bytes());
+        assert_eq!(b"h\xC3", longest_common_prefix_bytes(a, b));
+        assert_eq!(b"h\xC3", longest_common_prefix_slice(a, b));
+    }
+
+    #[test]
+    fn long_bytes() {
+        let a = [7; 100];
+
+        for i in 0..a.len() {
+            let mut b = a;
+            b[i] = 8;
+
+            assert_eq!(&a[..i], longest_common_prefix_bytes(&a, &b));
+            assert_eq!(&a[..i], longest_common_prefix_bytes(&a[..i], &b));
+            assert_eq!(&a[..i], longest_common_prefix_slice(&a, &b));
+        }
+    }
+
+    #[test]
+    fn same_ptr() {
+        let tokens = ["let", "x", "=", "1"];
+
+        assert_eq!(&tokens, longest_common_prefix_slice(&tokens, &tokens));
+    }
+
+    #[test]
+    fn tokens() {
+        let a = ["fn", "main", "(", ")"];
+        let b = ["fn", "main", "<", "T", ">"];
+
+        assert_eq!(["fn", "main"], longest_common_prefix_slice(&a, &b));
+    }
+
+    #[test]
+    fn empty_iterable() {
+        let iter: [&[u8]; 0] = [];
+
+        assert_eq!(None, longest_common_prefix_slice_in(iter));
+        assert_eq!(None, longest_common_prefix_bytes_in(iter));
+    }
+
+    #[test]
+    fn common_prefix_in_iterable() {
+        let lines: [&[&str]; 3] = [
+            &["#!/bin/sh", "set -e", "make"],
+            &["#!/bin/sh", "set -e", "make install"],
+            &["#!/bin/sh", "set -e"],
+        ];
+
+        assert_eq!(
+            Some(&["#!/bin/sh", "set -e"][..]),
+            longest_common_prefix_slice_in(lines)
+        );
+
+        let iter: [&[u8]; 3] = [b"/usr/\xFF/a", b"/usr/\xFF/b", b"/usr/\xFF"];
+
+        assert_eq!(
+            Some(&b"/usr/\xFF"[..]),
+            longest_common_prefix_bytes_in(iter)
+        );
+    }
+
+    #[test]
+    fn no_common_prefix_in_iterable() {
+        let iter: [&[u8]; 3] = [b"abc", b"", b"abd"];
+
+        assert_eq!(Some(&b""[..]), longest_common_prefix_slice_in(iter));
+        assert_eq!(Some(&b""[..]), longest_common_prefix_bytes_in(iter));
+    }
+}
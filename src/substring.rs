@@ -0,0 +1,315 @@
+// Small library to find a common prefix among strings.
+// Copyright (C) 2024  Sohum Mendon
+// SPDX-License-Identifier: MIT
+
+//! Longest common substrings, using a suffix automaton.
+//!
+//! The automaton is built for the first string, and every other string is
+//! run through it to find how much of each state it matches. This takes
+//! `O(n log σ)` time for `n` total `char`s from an alphabet of size `σ`.
+
+use alloc::collections::BTreeMap;
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Find the longest string that is a substring of both `a` and `b`.
+///
+/// This returns a slice of `a`, which can be the empty string `""` if
+/// the strings share no `char`. Lengths are counted in `char`s, and if
+/// there are several longest substrings, the leftmost one in `a` is
+/// returned.
+///
+/// ```rust
+/// use lcp::longest_common_substring;
+///
+/// let substring = longest_common_substring("xabcdy", "zzabcdzz");
+/// assert_eq!("abcd", substring);
+/// ```
+pub fn longest_common_substring<'a>(a: &'a str, b: &str) -> &'a str {
+    common_substring(a, [b])
+}
+
+/// Find the longest string that is a substring of every string in an
+/// iterable.
+///
+/// This returns [`None`] if the passed in iterable is empty. Otherwise,
+/// it returns a slice of the first string (including the empty string
+/// `""` if there is no common substring).
+pub fn longest_common_substring_in<'a>(iter: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    let mut iter = iter.into_iter();
+
+    let first = iter.next()?;
+
+    Some(common_substring(first, iter))
+}
+
+/// Find the longest substring of `first` that is also a substring of every
+/// string in `rest`.
+fn common_substring<'a, 'b>(first: &'a str, rest: impl IntoIterator<Item = &'b str>) -> &'a str {
+    let automaton = Automaton::new(first);
+
+    // The longest substring of every string seen so far, per state.
+    let mut common: Vec<usize> = automaton.states.iter().map(|s| s.len).collect();
+
+    for cur in rest {
+        let matched = automaton.matches(cur);
+
+        for (common, matched) in common.iter_mut().zip(matched) {
+            *common = (*common).min(matched);
+        }
+
+        if common.iter().all(|&len| len == 0) {
+            return "";
+        }
+    }
+
+    let (len, end) = automaton
+        .states
+        .iter()
+        .zip(&common)
+        .map(|(state, &len)| (len, state.end))
+        .max_by(|(len_a, end_a), (len_b, end_b)| len_a.cmp(len_b).then(end_b.cmp(end_a)))
+        .unwrap_or_default();
+
+    &first[automaton.offsets[end - len]..automaton.offsets[end]]
+}
+
+struct State {
+    /// The length (in `char`s) of the longest substring in this state.
+    len: usize,
+    /// The suffix link. Only the initial state has none.
+    link: Option<usize>,
+    next: BTreeMap<char, usize>,
+    /// Where (in `char`s) the first occurrence of this state's substrings
+    /// ends.
+    end: usize,
+}
+
+struct Automaton {
+    states: Vec<State>,
+    /// The byte offset of every `char` boundary in the source.
+    offsets: Vec<usize>,
+    /// State indices, sorted by decreasing `len`.
+    order: Vec<usize>,
+}
+
+impl Automaton {
+    fn new(s: &str) -> Self {
+        let mut states = vec![State {
+            len: 0,
+            link: None,
+            next: BTreeMap::new(),
+            end: 0,
+        }];
+        let mut offsets = vec![0];
+        let mut last = 0;
+
+        for (i, c) in s.char_indices() {
+            offsets.push(i + c.len_utf8());
+
+            let cur = states.len();
+            states.push(State {
+                len: states[last].len + 1,
+                link: None,
+                next: BTreeMap::new(),
+                end: offsets.len() - 1,
+            });
+
+            let mut p = Some(last);
+            while let Some(q) = p.filter(|&q| !states[q].next.contains_key(&c)) {
+                states[q].next.insert(c, cur);
+                p = states[q].link;
+            }
+
+            states[cur].link = Some(match p {
+                None => 0,
+                Some(p) => {
+                    let q = states[p].next[&c];
+                    if states[p].len + 1 == states[q].len {
+                        q
+                    } else {
+                        let clone = states.len();
+                        states.push(State {
+                            len: states[p].len + 1,
+                            link: states[q].link,
+                            next: states[q].next.clone(),
+                            end: states[q].end,
+                        });
+
+                        let mut p = Some(p);
+                        while let Some(r) = p.filter(|&r| states[r].next.get(&c) == Some(&q)) {
+                            states[r].next.insert(c, clone);
+                            p = states[r].link;
+                        }
+
+                        states[q].link = Some(clone);
+                        clone
+                    }
+                }
+            });
+
+            last = cur;
+        }
+
+        let mut order: Vec<usize> = (0..states.len()).collect();
+        order.sort_unstable_by_key(|&i| core::cmp::Reverse(states[i].len));
+
+        Automaton {
+            states,
+            offsets,
+            order,
+        }
+    }
+
+    /// Find, for each state, the length of the longest of its substrings
+    /// that also occurs in `s`.
+    fn matches(&self, s: &str) -> Vec<usize> {
+        let mut matched = vec![0; self.states.len()];
+        let (mut state, mut len) = (0, 0);
+
+        for c in s.chars() {
+            loop {
+                if let Some(&next) = self.states[state].next.get(&c) {
+                    state = next;
+                    len += 1;
+                    break;
+                }
+
+                if let Some(link) = self.states[state].link {
+                    state = link;
+                    len = self.states[link].len;
+                } else {
+                    len = 0;
+                    break;
+                }
+            }
+
+            matched[state] = matched[state].max(len);
+        }
+
+        // A match in a state is also a match of every suffix of it.
+        for &i in &self.order {
+            if let Some(link) = self.states[i].link {
+                if matched[i] > 0 {
+                    matched[link] = self.states[link].len;
+                }
+            }
+        }
+
+        matched
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn two_strings() {
+        assert_eq!("abcd", longest_common_substring("xabcdy", "zzabcdzz"));
+        assert_eq!("hello", longest_common_substring("hello", "hello"));
+        assert_eq!("", longest_common_substring("abc", "xyz"));
+        assert_eq!("", longest_common_substring("", "xyz"));
+        assert_eq!("", longest_common_substring("abc", ""));
+    }
+
+    #[test]
+    fn leftmost_in_first() {
+        assert_eq!("ab", longest_common_substring("abxcd", "cd ab"));
+        assert_eq!("a", longest_common_substring("aaa", "a"));
+    }
+
+    #[test]
+    fn repeated_characters() {
+        assert_eq!("aaa", longest_common_substring("baaab", "caaaac"));
+        assert_eq!("babab", longest_common_substring("abababab", "xbababx"));
+    }
+
+    #[test]
+    fn multi_byte() {
+        assert_eq!(
+            "日本語",
+            longest_common_substring("これは日本語です", "日本語の本")
+        );
+        // 'é' (C3 A9) and 'ê' (C3 AA) share a byte, but no char.
+        assert_eq!("", longest_common_substring("é", "ê"));
+        assert_eq!("😀x", longest_common_substring("a😀xb", "c😀xd"));
+    }
+
+    #[test]
+    fn empty_iterable() {
+        let iter = [];
+
+        assert_eq!(None, longest_common_substring_in(iter));
+    }
+
+    #[test]
+    fn one_element_in_iterable() {
+        assert_eq!(Some("alone"), longest_common_substring_in(["alone"]));
+    }
+
+    #[test]
+    fn common_substring_in_iterable() {
+        let iter = [
+            "2024-01-01 ERROR disk full on /dev/sda",
+            "2024-01-02 WARN retrying: disk full on /dev/sdb",
+            "ERROR: disk full on /dev/sdc, giving up",
+        ];
+
+        assert_eq!(
+            Some(" disk full on /dev/sd"),
+            longest_common_substring_in(iter)
+        );
+    }
+
+    #[test]
+    fn no_common_substring_in_iterable() {
+        let iter = ["abc", "bcd", "cde", "xyz"];
+
+        assert_eq!(Some(""), longest_common_substring_in(iter));
+    }
+
+    #[test]
+    fn matches_naive() {
+        fn naive<'a>(strs: &[&'a str]) -> &'a str {
+            let first = strs[0];
+            let mut best = "";
+
+            for (start, _) in first.char_indices() {
+                for end in (start + 1..=first.len()).filter(|&i| first.is_char_boundary(i)) {
+                    let candidate = &first[start..end];
+                    if candidate.chars().count() > best.chars().count()
+                        && strs.iter().all(|s| s.contains(candidate))
+                    {
+                        best = candidate;
+                    }
+                }
+            }
+
+            best
+        }
+
+        let strs = [
+            "abracadabra",
+            "cadabra",
+            "bracket",
+            "abcabcabc",
+            "dabrac",
+            "ééaé",
+            "aéé",
+        ];
+
+        for a in strs {
+            for b in strs {
+                for c in strs {
+                    let iter = [a, b, c];
+                    assert_eq!(
+                        Some(naive(&iter)),
+                        longest_common_substring_in(iter),
+                        "{iter:?}"
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,409 @@
+// Small library to find a common prefix among strings.
+// Copyright (C) 2024  Sohum Mendon
+// SPDX-License-Identifier: MIT
+
+//! Suffix arrays and LCP arrays.
+//!
+//! A suffix array lists the starting positions of every suffix of a text
+//! in sorted order. The matching LCP array holds the length of the longest
+//! common prefix of each suffix and the one before it.
+//!
+//! Suffixes are sorted by prefix doubling in `O(n log² n)` time, and the
+//! LCP array is built with Kasai's algorithm in `O(n)` time.
+//!
+//! ```rust
+//! use lcp::suffix::SuffixArray;
+//!
+//! let sa = SuffixArray::new(b"banana");
+//! assert_eq!(&[5, 3, 1, 0, 4, 2], sa.suffixes());
+//! assert_eq!(&[0, 1, 3, 0, 0, 2], sa.lcp());
+//! assert_eq!(b"ana", sa.longest_repeated());
+//! assert_eq!(15, sa.distinct_substrings());
+//! ```
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::{longest_common_prefix, longest_common_prefix_slice};
+
+/// A suffix array of a slice, with its LCP array.
+#[derive(Clone, Debug)]
+pub struct SuffixArray<'a, T> {
+    text: &'a [T],
+    suffixes: Vec<usize>,
+    lcp: Vec<usize>,
+}
+
+impl<'a, T: Ord> SuffixArray<'a, T> {
+    /// Build the suffix array and LCP array of `text`.
+    pub fn new(text: &'a [T]) -> Self {
+        let mut order: Vec<usize> = (0..text.len()).collect();
+        order.sort_unstable_by(|&i, &j| text[i].cmp(&text[j]));
+
+        let mut ranks = vec![0; text.len()];
+        let mut rank = 0;
+        for (k, &i) in order.iter().enumerate() {
+            if k == 0 || text[order[k - 1]] != text[i] {
+                rank += 1;
+            }
+            ranks[i] = rank;
+        }
+
+        let suffixes = sort_suffixes(ranks);
+        let lcp = kasai(&suffixes, |i, j, h| {
+            h + longest_common_prefix_slice(&text[i + h..], &text[j + h..]).len()
+        });
+
+        SuffixArray {
+            text,
+            suffixes,
+            lcp,
+        }
+    }
+
+    /// The text this suffix array was built for.
+    pub fn text(&self) -> &'a [T] {
+        self.text
+    }
+
+    /// The starting position of every suffix, in sorted order.
+    pub fn suffixes(&self) -> &[usize] {
+        &self.suffixes
+    }
+
+    /// The length of the longest common prefix of each suffix (in the
+    /// order of [`suffixes`](Self::suffixes)) and the one before it. The
+    /// first entry is always `0`.
+    pub fn lcp(&self) -> &[usize] {
+        &self.lcp
+    }
+
+    /// Find the longest slice that occurs at least twice in the text
+    /// (possibly overlapping).
+    ///
+    /// If there are several, the smallest one is returned. This is empty
+    /// if no element repeats.
+    pub fn longest_repeated(&self) -> &'a [T] {
+        let (start, len) = longest_repeated(&self.suffixes, &self.lcp);
+
+        &self.text[start..start + len]
+    }
+
+    /// Count the distinct non-empty slices of the text.
+    pub fn distinct_substrings(&self) -> usize {
+        let n = self.text.len();
+
+        n * (n + 1) / 2 - self.lcp.iter().sum::<usize>()
+    }
+}
+
+/// A suffix array of a string, with its LCP array.
+///
+/// Only suffixes starting on a `char` boundary are included, and every
+/// position and length is a byte offset that lies on a `char` boundary.
+#[derive(Clone, Debug)]
+pub struct StrSuffixArray<'a> {
+    text: &'a str,
+    suffixes: Vec<usize>,
+    lcp: Vec<usize>,
+    distinct: usize,
+    /// The byte offset and length of the longest repeated substring.
+    repeated: (usize, usize),
+}
+
+impl<'a> StrSuffixArray<'a> {
+    /// Build the suffix array and LCP array of `text`.
+    pub fn new(text: &'a str) -> Self {
+        let mut offsets: Vec<usize> = text.char_indices().map(|(i, _)| i).collect();
+        offsets.push(text.len());
+
+        // Every `char` is its own initial rank, as UTF-8 sorts like code
+        // points do.
+        let ranks = text.chars().map(|c| c as usize + 1).collect();
+        let by_char = sort_suffixes(ranks);
+
+        // Lengths in `char`s, extended with the crate's own prefix search.
+        let char_lcp = kasai(&by_char, |i, j, h| {
+            let common = longest_common_prefix(&text[offsets[i + h]..], &text[offsets[j + h]..]);
+            h + common.chars().count()
+        });
+
+        let n = offsets.len() - 1;
+        let distinct = n * (n + 1) / 2 - char_lcp.iter().sum::<usize>();
+
+        let (start, len) = longest_repeated(&by_char, &char_lcp);
+        let repeated = (offsets[start], offsets[start + len] - offsets[start]);
+
+        let lcp = by_char
+            .iter()
+            .zip(&char_lcp)
+            .map(|(&i, &h)| offsets[i + h] - offsets[i])
+            .collect();
+        let suffixes = by_char.iter().map(|&i| offsets[i]).collect();
+
+        StrSuffixArray {
+            text,
+            suffixes,
+            lcp,
+            distinct,
+            repeated,
+        }
+    }
+
+    /// The text this suffix array was built for.
+    pub fn text(&self) -> &'a str {
+        self.text
+    }
+
+    /// The byte offset of every suffix, in sorted order.
+    pub fn suffixes(&self) -> &[usize] {
+        &self.suffixes
+    }
+
+    /// The byte length of the longest common prefix of each suffix (in
+    /// the order of [`suffixes`](Self::suffixes)) and the one before it.
+    /// The first entry is always `0`.
+    pub fn lcp(&self) -> &[usize] {
+        &self.lcp
+    }
+
+    /// Find the longest substring that occurs at least twice in the text
+    /// (possibly overlapping).
+    ///
+    /// Lengths are counted in `char`s, and if there are several longest
+    /// substrings, the smallest one is returned. This is the empty string
+    /// `""` if no `char` repeats.
+    pub fn longest_repeated(&self) -> &'a str {
+        let (start, len) = self.repeated;
+
+        &self.text[start..start + len]
+    }
+
+    /// Count the distinct non-empty substrings of the text.
+    ///
+    /// Substrings are made of whole `char`s.
+    pub fn distinct_substrings(&self) -> usize {
+        self.distinct
+    }
+}
+
+/// Sort the suffixes of a text by prefix doubling. `ranks` holds the rank
+/// of each element of the text, starting from `1`.
+fn sort_suffixes(mut ranks: Vec<usize>) -> Vec<usize> {
+    let n = ranks.len();
+    let mut suffixes: Vec<usize> = (0..n).collect();
+    let mut next = vec![0; n];
+    let mut k = 1;
+
+    loop {
+        // Rank `0` sorts a suffix that ends within `k` before the rest.
+        let key = |i: usize| (ranks[i], ranks.get(i + k).copied().unwrap_or(0));
+        suffixes.sort_unstable_by_key(|&i| key(i));
+
+        let mut rank = 0;
+        for (pos, &i) in suffixes.iter().enumerate() {
+            if pos == 0 || key(suffixes[pos - 1]) != key(i) {
+                rank += 1;
+            }
+            next[i] = rank;
+        }
+
+        core::mem::swap(&mut ranks, &mut next);
+
+        if rank == n || k >= n {
+            return suffixes;
+        }
+
+        k *= 2;
+    }
+}
+
+/// Build the LCP array of `suffixes` with Kasai's algorithm.
+///
+/// `extend(i, j, h)` returns the length of the common prefix of the
+/// suffixes at `i` and `j`, which are known to share at least `h`.
+fn kasai(suffixes: &[usize], mut extend: impl FnMut(usize, usize, usize) -> usize) -> Vec<usize> {
+    let n = suffixes.len();
+    let mut rank = vec![0; n];
+    for (pos, &i) in suffixes.iter().enumerate() {
+        rank[i] = pos;
+    }
+
+    let mut lcp = vec![0; n];
+    let mut h = 0;
+
+    for i in 0..n {
+        if rank[i] == 0 {
+            h = 0;
+            continue;
+        }
+
+        let j = suffixes[rank[i] - 1];
+        h = extend(i, j, h);
+        lcp[rank[i]] = h;
+
+        h = h.saturating_sub(1);
+    }
+
+    lcp
+}
+
+/// The start and length of the first longest entry in an LCP array.
+fn longest_repeated(suffixes: &[usize], lcp: &[usize]) -> (usize, usize) {
+    suffixes.iter().zip(lcp).fold(
+        (0, 0),
+        |best, (&start, &len)| {
+            if len > best.1 {
+                (start, len)
+            } else {
+                best
+            }
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::collections::BTreeSet;
+
+    /// Sort every suffix by comparison, which is `O(n² log n)`.
+    fn naive(text: &str) -> (Vec<usize>, Vec<usize>) {
+        let mut suffixes: Vec<usize> = text.char_indices().map(|(i, _)| i).collect();
+        suffixes.sort_by_key(|&i| &text[i..]);
+
+        let lcp = suffixes
+            .iter()
+            .enumerate()
+            .map(|(pos, &i)| match pos {
+                0 => 0,
+                _ => longest_common_prefix(&text[suffixes[pos - 1]..], &text[i..]).len(),
+            })
+            .collect();
+
+        (suffixes, lcp)
+    }
+
+    fn naive_distinct(text: &str) -> usize {
+        let mut seen = BTreeSet::new();
+        for (start, _) in text.char_indices() {
+            for (end, c) in text[start..].char_indices() {
+                seen.insert(&text[start..start + end + c.len_utf8()]);
+            }
+        }
+
+        seen.len()
+    }
+
+    fn naive_longest_repeated(text: &str) -> &str {
+        let mut best = "";
+        for (start, _) in text.char_indices() {
+            for (other, _) in text.char_indices().filter(|&(i, _)| i != start) {
+                let common = longest_common_prefix(&text[start..], &text[other..]);
+                let (len, best_len) = (common.chars().count(), best.chars().count());
+                if len > best_len || (len == best_len && common < best) {
+                    best = common;
+                }
+            }
+        }
+
+        best
+    }
+
+    const TEXTS: [&str; 10] = [
+        "",
+        "a",
+        "aaaa",
+        "banana",
+        "mississippi",
+        "abracadabra",
+        "abcabcabcabd",
+        "日本語の日本",
+        "éaéaé😀é",
+        "the quick brown fox jumps over the lazy dog",
+    ];
+
+    #[test]
+    fn banana() {
+        let sa = SuffixArray::new(b"banana");
+
+        assert_eq!(&[5, 3, 1, 0, 4, 2], sa.suffixes());
+        assert_eq!(&[0, 1, 3, 0, 0, 2], sa.lcp());
+        assert_eq!(b"ana", sa.longest_repeated());
+        assert_eq!(15, sa.distinct_substrings());
+    }
+
+    #[test]
+    fn empty() {
+        let sa = SuffixArray::<u8>::new(&[]);
+
+        assert!(sa.suffixes().is_empty());
+        assert!(sa.lcp().is_empty());
+        assert!(sa.longest_repeated().is_empty());
+        assert_eq!(0, sa.distinct_substrings());
+    }
+
+    #[test]
+    fn tokens() {
+        let text = ["a", "b", "a", "b", "c"];
+        let sa = SuffixArray::new(&text);
+
+        assert_eq!(["a", "b"], sa.longest_repeated());
+        assert_eq!(12, sa.distinct_substrings());
+    }
+
+    #[test]
+    fn bytes_match_naive() {
+        for text in TEXTS {
+            let bytes = text.as_bytes();
+            let sa = SuffixArray::new(bytes);
+
+            let mut suffixes: Vec<usize> = (0..bytes.len()).collect();
+            suffixes.sort_by_key(|&i| &bytes[i..]);
+
+            assert_eq!(suffixes, sa.suffixes(), "{text:?}");
+            for (pos, &len) in sa.lcp().iter().enumerate().skip(1) {
+                let (a, b) = (&bytes[suffixes[pos - 1]..], &bytes[suffixes[pos]..]);
+                assert_eq!(longest_common_prefix_slice(a, b).len(), len, "{text:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn str_matches_naive() {
+        for text in TEXTS {
+            let sa = StrSuffixArray::new(text);
+            let (suffixes, lcp) = naive(text);
+
+            assert_eq!(suffixes, sa.suffixes(), "{text:?}");
+            assert_eq!(lcp, sa.lcp(), "{text:?}");
+            assert_eq!(naive_distinct(text), sa.distinct_substrings(), "{text:?}");
+            assert_eq!(
+                naive_longest_repeated(text),
+                sa.longest_repeated(),
+                "{text:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn str_longest_repeated() {
+        assert_eq!(
+            "issi",
+            StrSuffixArray::new("mississippi").longest_repeated()
+        );
+        assert_eq!(
+            "日本",
+            StrSuffixArray::new("日本語の日本").longest_repeated()
+        );
+        assert_eq!("", StrSuffixArray::new("abc").longest_repeated());
+    }
+
+    #[test]
+    fn str_counts_chars() {
+        // Three distinct chars, and every substring is distinct.
+        assert_eq!(6, StrSuffixArray::new("é😀x").distinct_substrings());
+        assert_eq!(6, SuffixArray::new(b"abc").distinct_substrings());
+    }
+}
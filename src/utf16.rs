@@ -0,0 +1,221 @@
+// Small library to find a common prefix among strings.
+// Copyright (C) 2024  Sohum Mendon
+// SPDX-License-Identifier: MIT
+
+//! Common prefixes of UTF-16 code unit buffers.
+//!
+//! The buffers don't have to be valid UTF-16. A prefix never ends between
+//! a high and a low surrogate, but unpaired surrogates are compared like
+//! any other code unit.
+
+use core::char::{decode_utf16, REPLACEMENT_CHARACTER};
+
+use crate::longest_common_prefix_slice;
+
+/// Find the longest common prefix between two UTF-16 buffers.
+///
+/// This returns a prefix of `a`, which is empty if there is no common
+/// prefix. It never ends between the two halves of a surrogate pair in
+/// either buffer.
+///
+/// ```rust
+/// use lcp::longest_common_prefix_utf16;
+///
+/// let a: Vec<u16> = "file-\u{1F600}".encode_utf16().collect();
+/// let b: Vec<u16> = "file-\u{1F601}".encode_utf16().collect();
+///
+/// // Both emoji start with the same high surrogate, which isn't included.
+/// assert_eq!(&a[..5], longest_common_prefix_utf16(&a, &b));
+/// ```
+pub fn longest_common_prefix_utf16<'a>(a: &'a [u16], b: &[u16]) -> &'a [u16] {
+    let mut len = longest_common_prefix_slice(a, b).len();
+
+    let splits_pair = |units: &[u16]| units.get(len).is_some_and(|&u| is_low_surrogate(u));
+
+    if len > 0 && is_high_surrogate(a[len - 1]) && (splits_pair(a) || splits_pair(b)) {
+        len -= 1;
+    }
+
+    &a[..len]
+}
+
+/// Find the longest prefix in an iterable of UTF-16 buffers.
+///
+/// This returns [`None`] if the passed in iterable is empty. Otherwise,
+/// it returns a slice (which is empty if there is no common prefix).
+pub fn longest_common_prefix_utf16_in<'a>(
+    iter: impl IntoIterator<Item = &'a [u16]>,
+) -> Option<&'a [u16]> {
+    let mut iter = iter.into_iter();
+
+    let mut lcp = iter.next()?;
+
+    for cur in iter {
+        lcp = longest_common_prefix_utf16(lcp, cur);
+
+        if lcp.is_empty() {
+            return Some(lcp);
+        }
+    }
+
+    Some(lcp)
+}
+
+/// Convert an index into a UTF-16 buffer to a byte offset into its UTF-8
+/// equivalent.
+///
+/// Unpaired surrogates count as U+FFFD REPLACEMENT CHARACTER, as in
+/// `String::from_utf16_lossy`.
+///
+/// # Panics
+///
+/// Panics if `index` is greater than the length of `units`, or if it is
+/// between the two halves of a surrogate pair.
+///
+/// ```rust
+/// use lcp::utf16_to_utf8_offset;
+///
+/// let units: Vec<u16> = "h\u{E9}\u{1F600}!".encode_utf16().collect();
+///
+/// assert_eq!(3, utf16_to_utf8_offset(&units, 2));
+/// assert_eq!(7, utf16_to_utf8_offset(&units, 4));
+/// ```
+pub fn utf16_to_utf8_offset(units: &[u16], index: usize) -> usize {
+    let splits_pair = index > 0
+        && is_high_surrogate(units[index - 1])
+        && units.get(index).is_some_and(|&u| is_low_surrogate(u));
+    assert!(
+        !splits_pair,
+        "index {index} is between the two halves of a surrogate pair"
+    );
+
+    decode_utf16(units[..index].iter().copied())
+        .map(|c| c.unwrap_or(REPLACEMENT_CHARACTER).len_utf8())
+        .sum()
+}
+
+/// Convert a byte offset into a string to an index into its UTF-16
+/// equivalent.
+///
+/// # Panics
+///
+/// Panics if `offset` is not a `char` boundary of `s`.
+///
+/// ```rust
+/// use lcp::utf8_to_utf16_offset;
+///
+/// assert_eq!(4, utf8_to_utf16_offset("h\u{E9}\u{1F600}!", 7));
+/// ```
+pub fn utf8_to_utf16_offset(s: &str, offset: usize) -> usize {
+    s[..offset].chars().map(char::len_utf16).sum()
+}
+
+fn is_high_surrogate(unit: u16) -> bool {
+    (0xD800..0xDC00).contains(&unit)
+}
+
+fn is_low_surrogate(unit: u16) -> bool {
+    (0xDC00..0xE000).contains(&unit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Encode `s` as UTF-16 into `buf`, and return the used part.
+    fn encode<'a>(s: &str, buf: &'a mut [u16; 32]) -> &'a [u16] {
+        let mut len = 0;
+        for unit in s.encode_utf16() {
+            buf[len] = unit;
+            len += 1;
+        }
+
+        &buf[..len]
+    }
+
+    #[test]
+    fn bmp() {
+        let (mut a, mut b) = ([0; 32], [0; 32]);
+        let (a, b) = (encode("hello", &mut a), encode("help", &mut b));
+
+        assert_eq!(&a[..3], longest_common_prefix_utf16(a, b));
+        assert_eq!(a, longest_common_prefix_utf16(a, a));
+        assert_eq!(&a[..0], longest_common_prefix_utf16(a, &[]));
+    }
+
+    #[test]
+    fn surrogate_pairs() {
+        let (mut a, mut b) = ([0; 32], [0; 32]);
+        let (a, b) = (encode("x\u{1F600}y", &mut a), encode("x\u{1F601}y", &mut b));
+
+        assert_eq!(a[1], b[1]);
+        assert_eq!(&a[..1], longest_common_prefix_utf16(a, b));
+
+        let (mut a, mut b) = ([0; 32], [0; 32]);
+        let (a, b) = (encode("x\u{1F600}y", &mut a), encode("x\u{1F600}", &mut b));
+
+        assert_eq!(&a[..3], longest_common_prefix_utf16(a, b));
+    }
+
+    #[test]
+    fn pair_split_in_second() {
+        // `a` ends with a high surrogate, which starts a pair in `b`.
+        let a = [0x78, 0xD83D];
+        let b = [0x78, 0xD83D, 0xDE00];
+
+        assert_eq!(&a[..1], longest_common_prefix_utf16(&a, &b));
+    }
+
+    #[test]
+    fn unpaired_surrogates() {
+        let a = [0x78, 0xD83D, 0x79];
+        let b = [0x78, 0xD83D, 0x7A];
+
+        assert_eq!(&a[..2], longest_common_prefix_utf16(&a, &b));
+
+        let a = [0xDE00, 0x79];
+        let b = [0xDE00, 0x7A];
+
+        assert_eq!(&a[..1], longest_common_prefix_utf16(&a, &b));
+    }
+
+    #[test]
+    fn offsets() {
+        let mut buf = [0; 32];
+        let s = "a\u{E9}\u{20AC}\u{1F600}z";
+        let units = encode(s, &mut buf);
+
+        for (i, _) in s.char_indices().chain([(s.len(), ' ')]) {
+            let index = utf8_to_utf16_offset(s, i);
+            assert_eq!(i, utf16_to_utf8_offset(units, index));
+        }
+
+        // An unpaired surrogate is replaced with U+FFFD, which is 3 bytes.
+        assert_eq!(4, utf16_to_utf8_offset(&[0x78, 0xD83D], 2));
+    }
+
+    #[test]
+    #[should_panic(expected = "between the two halves of a surrogate pair")]
+    fn offset_splits_pair() {
+        utf16_to_utf8_offset(&[0xD83D, 0xDE00], 1);
+    }
+
+    #[test]
+    fn empty_iterable() {
+        let iter = [];
+
+        assert_eq!(None, longest_common_prefix_utf16_in(iter));
+    }
+
+    #[test]
+    fn common_prefix_in_iterable() {
+        let (mut a, mut b, mut c) = ([0; 32], [0; 32], [0; 32]);
+        let iter = [
+            encode("caf\u{E9} \u{1F600}1", &mut a),
+            encode("caf\u{E9} \u{1F600}2", &mut b),
+            encode("caf\u{E9} \u{1F601}", &mut c),
+        ];
+
+        assert_eq!(Some(&iter[0][..5]), longest_common_prefix_utf16_in(iter));
+    }
+}
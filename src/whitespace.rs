@@ -0,0 +1,173 @@
+// Small library to find a common prefix among strings.
+// Copyright (C) 2024  Sohum Mendon
+// SPDX-License-Identifier: MIT
+
+//! Whitespace-insensitive common prefixes.
+//!
+//! Any run of Unicode whitespace compares equal to any other run, using
+//! [`CollapseWhitespace`]. The `_trim_start` functions also ignore
+//! whitespace at the start of each string, using [`TrimStart`].
+
+use crate::longest_common_prefix_with;
+use crate::normalize::{CollapseWhitespace, Normalizer, TrimStart};
+
+/// Find the longest common prefix between two strings, treating every run
+/// of whitespace as equal.
+///
+/// This returns a prefix of `a`, which can be the empty string `""` if
+/// there is no common prefix. A run of whitespace is only included if it
+/// is matched by a run in `b`.
+///
+/// ```rust
+/// use lcp::longest_common_prefix_ignore_whitespace;
+///
+/// let prefix = longest_common_prefix_ignore_whitespace("ls  -l\t/tmp", "ls -l /usr");
+/// assert_eq!("ls  -l\t/", prefix);
+/// ```
+pub fn longest_common_prefix_ignore_whitespace<'a>(a: &'a str, b: &str) -> &'a str {
+    longest_common_prefix_with(&CollapseWhitespace, a, b)
+}
+
+/// Find the longest prefix in an iterable, treating every run of
+/// whitespace as equal.
+///
+/// This returns [`None`] if the passed in iterable is empty. Otherwise,
+/// it returns a prefix of the first string (including the empty string
+/// `""` if there is no common prefix).
+pub fn longest_common_prefix_ignore_whitespace_in<'a>(
+    iter: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+    let mut iter = iter.into_iter();
+
+    let mut lcp = iter.next()?;
+
+    for cur in iter {
+        lcp = longest_common_prefix_ignore_whitespace(lcp, cur);
+
+        if lcp.is_empty() {
+            return Some(lcp);
+        }
+    }
+
+    Some(lcp)
+}
+
+/// Find the longest common prefix between two strings, ignoring whitespace
+/// at the start and treating every other run of whitespace as equal.
+///
+/// This returns a prefix of `a`, which can be the empty string `""` if
+/// there is no common prefix. The whitespace at the start of `a` is only
+/// included if something after it matched.
+///
+/// ```rust
+/// use lcp::longest_common_prefix_ignore_whitespace_trim_start;
+///
+/// let prefix = longest_common_prefix_ignore_whitespace_trim_start("  key =  1", "key = 2");
+/// assert_eq!("  key =  ", prefix);
+/// ```
+pub fn longest_common_prefix_ignore_whitespace_trim_start<'a>(a: &'a str, b: &str) -> &'a str {
+    longest_common_prefix_with(&TrimStart.then(CollapseWhitespace), a, b)
+}
+
+/// Find the longest prefix in an iterable, ignoring whitespace at the start
+/// and treating every other run of whitespace as equal.
+///
+/// This returns [`None`] if the passed in iterable is empty. Otherwise,
+/// it returns a prefix of the first string (including the empty string
+/// `""` if there is no common prefix).
+pub fn longest_common_prefix_ignore_whitespace_trim_start_in<'a>(
+    iter: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+    let mut iter = iter.into_iter();
+
+    let mut lcp = iter.next()?;
+
+    for cur in iter {
+        lcp = longest_common_prefix_ignore_whitespace_trim_start(lcp, cur);
+
+        if lcp.is_empty() {
+            return Some(lcp);
+        }
+    }
+
+    Some(lcp)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn runs_of_whitespace() {
+        assert_eq!(
+            "a  b\t",
+            longest_common_prefix_ignore_whitespace("a  b\tc", "a b d")
+        );
+        assert_eq!(
+            "a \u{3000}\u{A0}",
+            longest_common_prefix_ignore_whitespace("a \u{3000}\u{A0}b", "a\nc")
+        );
+        assert_eq!(
+            "x = 1",
+            longest_common_prefix_ignore_whitespace("x = 1", "x\t=\t1")
+        );
+    }
+
+    #[test]
+    fn whitespace_against_none() {
+        assert_eq!("a", longest_common_prefix_ignore_whitespace("a b", "ab"));
+        assert_eq!("ab", longest_common_prefix_ignore_whitespace("ab", "ab  "));
+        assert_eq!("ab", longest_common_prefix_ignore_whitespace("ab  ", "ab"));
+    }
+
+    #[test]
+    fn leading_whitespace() {
+        assert_eq!("", longest_common_prefix_ignore_whitespace("  a", "a"));
+        assert_eq!("  a", longest_common_prefix_ignore_whitespace("  a", "\ta"));
+    }
+
+    #[test]
+    fn trim_start() {
+        let trim = longest_common_prefix_ignore_whitespace_trim_start;
+
+        assert_eq!("  a", trim("  a", "a"));
+        assert_eq!("a", trim("a", "\t\ta"));
+        assert_eq!("\n a  b\t", trim("\n a  b\tc", "a b d"));
+        // Only leading whitespace matched, so nothing is kept.
+        assert_eq!("", trim("  a", "  b"));
+        assert_eq!("", trim("  ", "a"));
+    }
+
+    #[test]
+    fn empty_iterable() {
+        let iter = [];
+
+        assert_eq!(None, longest_common_prefix_ignore_whitespace_in(iter));
+        assert_eq!(
+            None,
+            longest_common_prefix_ignore_whitespace_trim_start_in(iter)
+        );
+    }
+
+    #[test]
+    fn common_prefix_in_iterable() {
+        let iter = [
+            "cargo  build --release",
+            "cargo build\t--all",
+            "cargo build  --",
+        ];
+
+        assert_eq!(
+            Some("cargo  build --"),
+            longest_common_prefix_ignore_whitespace_in(iter)
+        );
+
+        let iter = ["  if x {", "if  x {}", "\tif x  y"];
+
+        assert_eq!(
+            Some("  if x "),
+            longest_common_prefix_ignore_whitespace_trim_start_in(iter)
+        );
+        assert_eq!(Some(""), longest_common_prefix_ignore_whitespace_in(iter));
+    }
+}